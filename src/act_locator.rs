@@ -0,0 +1,273 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeSet, ops::RangeInclusive, path::Path};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::{
+    identifier::ActIdentifier,
+    mk_downloader::MkIssue,
+    mk_index::{MkIndex, MkIndexRecord},
+};
+
+/// The default index shipped with Hun-Law
+pub const DEFAULT_INDEX_PATH: &str = "./data/act_to_mk_issue.csv";
+
+/// Where a document was published
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActLocation {
+    pub mk_issue: MkIssue,
+    /// PDF page indices of the document in the issue, if known
+    pub pages: Option<RangeInclusive<usize>>,
+    /// Name of the source this location came from
+    pub source: String,
+}
+
+/// A document that different sources place into different MK issues
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ambiguity {
+    pub act: ActIdentifier,
+    pub candidates: Vec<ActLocation>,
+}
+
+#[derive(Debug, Clone)]
+struct LocatorSource {
+    name: String,
+    index: MkIndex,
+    is_override: bool,
+}
+
+/// Finds the MK issue of documents, based on one or more indexes.
+///
+/// Overrides take precedence over all other sources, in the order they were added.
+/// If the other sources disagree about a document, it is reported as ambiguous.
+#[derive(Debug, Clone, Default)]
+pub struct ActLocator {
+    sources: Vec<LocatorSource>,
+}
+
+impl ActLocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A locator using only the default index
+    pub fn from_default_index() -> Result<Self> {
+        let mut result = Self::new();
+        result.add_csv(Path::new(DEFAULT_INDEX_PATH))?;
+        Ok(result)
+    }
+
+    pub fn add_source(&mut self, name: impl Into<String>, index: MkIndex) {
+        self.sources.push(LocatorSource {
+            name: name.into(),
+            index,
+            is_override: false,
+        });
+    }
+
+    /// Add an index stored in a CSV file, see [`MkIndex`]
+    pub fn add_csv(&mut self, path: &Path) -> Result<()> {
+        self.add_source(path.to_string_lossy(), MkIndex::load(path)?);
+        Ok(())
+    }
+
+    /// Add manually curated records that take precedence over the other sources
+    pub fn add_overrides(&mut self, name: impl Into<String>, index: MkIndex) {
+        self.sources.push(LocatorSource {
+            name: name.into(),
+            index,
+            is_override: true,
+        });
+    }
+
+    /// All locations of `act` from all sources, overrides first
+    pub fn candidates(&self, act: ActIdentifier) -> Vec<ActLocation> {
+        let (overrides, others): (Vec<_>, Vec<_>) =
+            self.sources.iter().partition(|source| source.is_override);
+        overrides
+            .into_iter()
+            .chain(others)
+            .filter_map(|source| {
+                source
+                    .index
+                    .get(act)
+                    .map(|record| ActLocation::new(record, &source.name))
+            })
+            .collect()
+    }
+
+    pub fn locate(&self, act: ActIdentifier) -> Result<ActLocation> {
+        if let Some(location) = self.override_for(act) {
+            return Ok(location);
+        }
+        let candidates = self.candidates(act);
+        if let Some(ambiguity) = Ambiguity::from_candidates(act, &candidates) {
+            bail!("Ambiguous MK issue for {act}: {:?}", ambiguity.candidates);
+        }
+        // All candidates are in the same issue, prefer the ones with known pages
+        candidates
+            .iter()
+            .find(|location| location.pages.is_some())
+            .or_else(|| candidates.first())
+            .cloned()
+            .ok_or_else(|| anyhow!("Could not find the MK issue of {act}"))
+    }
+
+    /// The MK issues that contain `acts`, without duplicates
+    pub fn issues_of(&self, acts: &[ActIdentifier]) -> Result<Vec<MkIssue>> {
+        let mut result = BTreeSet::new();
+        for act in acts {
+            result.insert(self.locate(*act)?.mk_issue);
+        }
+        Ok(result.into_iter().collect())
+    }
+
+    /// All documents which are in different MK issues in different sources,
+    /// and are not resolved by an override.
+    pub fn ambiguities(&self) -> Vec<Ambiguity> {
+        let acts: BTreeSet<ActIdentifier> = self
+            .sources
+            .iter()
+            .filter(|source| !source.is_override)
+            .flat_map(|source| source.index.iter().map(|record| record.act))
+            .collect();
+        acts.into_iter()
+            .filter(|act| self.override_for(*act).is_none())
+            .filter_map(|act| Ambiguity::from_candidates(act, &self.candidates(act)))
+            .collect()
+    }
+
+    fn override_for(&self, act: ActIdentifier) -> Option<ActLocation> {
+        self.sources
+            .iter()
+            .filter(|source| source.is_override)
+            .find_map(|source| {
+                source
+                    .index
+                    .get(act)
+                    .map(|record| ActLocation::new(record, &source.name))
+            })
+    }
+}
+
+impl ActLocation {
+    fn new(record: &MkIndexRecord, source: &str) -> Self {
+        Self {
+            mk_issue: record.mk_issue.clone(),
+            pages: record
+                .first_page
+                .zip(record.last_page)
+                .map(|(first, last)| first..=last),
+            source: source.to_owned(),
+        }
+    }
+}
+
+impl Ambiguity {
+    fn from_candidates(act: ActIdentifier, candidates: &[ActLocation]) -> Option<Self> {
+        let issues: BTreeSet<&MkIssue> = candidates.iter().map(|c| &c.mk_issue).collect();
+        if issues.len() > 1 {
+            Some(Self {
+                act,
+                candidates: candidates.to_vec(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(records: &[(&str, &str, Option<usize>)]) -> MkIndex {
+        let mut result = MkIndex::new();
+        for (mk_issue, act, first_page) in records {
+            result.insert(MkIndexRecord {
+                mk_issue: mk_issue.parse().unwrap(),
+                act: act.parse().unwrap(),
+                title: String::new(),
+                first_page: *first_page,
+                last_page: first_page.map(|p| p + 2),
+            });
+        }
+        result
+    }
+
+    #[test]
+    fn test_locate() {
+        let mut locator = ActLocator::new();
+        locator.add_source(
+            "csv",
+            index(&[
+                ("2013/31", "2013. évi V. törvény", None),
+                ("2013/40", "2013. évi X. törvény", None),
+                ("2013/50", "2013. évi XX. törvény", None),
+            ]),
+        );
+        locator.add_source(
+            "pdf",
+            index(&[
+                ("2013/31", "2013. évi V. törvény", Some(10)),
+                ("2013/41", "2013. évi X. törvény", Some(3)),
+                ("2013/51", "2013. évi XX. törvény", Some(5)),
+            ]),
+        );
+        locator.add_overrides(
+            "manual",
+            index(&[("2013/52", "2013. évi XX. törvény", None)]),
+        );
+
+        let ptk = locator
+            .locate("2013. évi V. törvény".parse().unwrap())
+            .unwrap();
+        assert_eq!(ptk.mk_issue, "2013/31".parse().unwrap());
+        assert_eq!(ptk.pages, Some(10..=12));
+        assert_eq!(ptk.source, "pdf");
+
+        let overridden = locator
+            .locate("2013. évi XX. törvény".parse().unwrap())
+            .unwrap();
+        assert_eq!(overridden.mk_issue, "2013/52".parse().unwrap());
+        assert_eq!(overridden.source, "manual");
+
+        assert!(locator
+            .locate("2013. évi X. törvény".parse().unwrap())
+            .is_err());
+        assert!(locator
+            .locate("2013. évi XI. törvény".parse().unwrap())
+            .is_err());
+
+        let ambiguities = locator.ambiguities();
+        assert_eq!(ambiguities.len(), 1);
+        assert_eq!(ambiguities[0].act, "2013. évi X. törvény".parse().unwrap());
+        assert_eq!(ambiguities[0].candidates.len(), 2);
+
+        assert_eq!(
+            locator
+                .issues_of(&[
+                    "2013. évi V. törvény".parse().unwrap(),
+                    "2013. évi XX. törvény".parse().unwrap(),
+                    "2013. évi V. törvény".parse().unwrap(),
+                ])
+                .unwrap(),
+            vec!["2013/31".parse().unwrap(), "2013/52".parse().unwrap()]
+        );
+    }
+}
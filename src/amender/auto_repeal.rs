@@ -0,0 +1,114 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+
+use super::{enforcement_date::EnforcementDateSet, repeal::repeal_article};
+use crate::{
+    identifier::IdentifierCommon,
+    reference::Reference,
+    semantic_info::{SemanticInfo, SpecialPhrase},
+    structure::{
+        Act, ChangeCause, ChildrenCommon, LastChange, Paragraph, SAEBody, SubArticleElement,
+    },
+    util::walker::SAEVisitorMut,
+};
+
+impl Act {
+    /// Repeal the amending provisions that are no longer in force on `date`.
+    ///
+    /// According to Jat. 12. § (2), amending provisions and repeals lose force on the
+    /// day after they come into force. Articles that only consisted of such provisions
+    /// are repealed as a whole.
+    pub fn apply_auto_repeals(&mut self, date: NaiveDate) -> Result<()> {
+        let enforcement_dates = EnforcementDateSet::from_act(self)?;
+        self.apply_auto_repeals_with(&enforcement_dates, date)
+    }
+
+    /// Same as `apply_auto_repeals`, but with already resolved enforcement dates.
+    pub(super) fn apply_auto_repeals_with(
+        &mut self,
+        enforcement_dates: &EnforcementDateSet,
+        date: NaiveDate,
+    ) -> Result<()> {
+        let mut visitor = AutoRepealVisitor {
+            enforcement_dates,
+            date,
+        };
+        self.walk_saes_mut(&mut visitor)?;
+        for article in self.articles_mut() {
+            if let Some(change) = common_auto_repeal(&article.children) {
+                repeal_article(article, &change);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// If all paragraphs were auto repealed, return the latest of those changes
+fn common_auto_repeal(paragraphs: &[Paragraph]) -> Option<LastChange> {
+    paragraphs
+        .iter()
+        .map(|paragraph| match &paragraph.last_change {
+            Some(
+                change @ LastChange {
+                    cause: ChangeCause::AutoRepeal,
+                    ..
+                },
+            ) if paragraph.body == SAEBody::Text(String::new()) => Some(change),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .max_by_key(|change| change.date)
+        .cloned()
+}
+
+struct AutoRepealVisitor<'a> {
+    enforcement_dates: &'a EnforcementDateSet,
+    date: NaiveDate,
+}
+
+impl<'a> SAEVisitorMut for AutoRepealVisitor<'a> {
+    fn on_enter<IT: IdentifierCommon, CT: ChildrenCommon>(
+        &mut self,
+        position: &Reference,
+        element: &mut SubArticleElement<IT, CT>,
+    ) -> Result<()> {
+        if !matches!(
+            element.semantic_info.special_phrase,
+            Some(
+                SpecialPhrase::BlockAmendment(_)
+                    | SpecialPhrase::StructuralBlockAmendment(_)
+                    | SpecialPhrase::TextAmendment(_)
+                    | SpecialPhrase::Repeal(_)
+            )
+        ) {
+            return Ok(());
+        }
+        let repeal_date = self.enforcement_dates.get_enforcement_date(position) + Duration::days(1);
+        if repeal_date <= self.date {
+            element.body = SAEBody::Text(String::new());
+            element.semantic_info = SemanticInfo::default();
+            element.last_change = Some(LastChange {
+                date: repeal_date,
+                cause: ChangeCause::AutoRepeal,
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{anyhow, bail, ensure, Result};
+use serde::{Deserialize, Serialize};
+
+use super::ModifyAct;
+use crate::{
+    identifier::{
+        range::{IdentifierRange, IdentifierRangeFrom},
+        ActIdentifier, AlphabeticIdentifier, IdentifierCommon, NumericIdentifier,
+        ParagraphIdentifier,
+    },
+    reference::{
+        parts::{RefPartParagraph, RefPartPoint},
+        Reference,
+    },
+    structure::{
+        Act, AlphabeticPoint, AlphabeticPointChildren, Article, BlockAmendmentChildren,
+        ChildrenCommon, LastChange, NumericPoint, NumericPointChildren, Paragraph,
+        ParagraphChildren, SAEBody, SubArticleElement,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockAmendmentWithContent {
+    pub position: Reference,
+    pub pure_insertion: bool,
+    pub content: BlockAmendmentChildren,
+}
+
+impl ModifyAct for BlockAmendmentWithContent {
+    fn apply(&self, act: &mut Act, change: &LastChange) -> Result<()> {
+        let article_id = self
+            .position
+            .article()
+            .ok_or_else(|| anyhow!("No article in block amendment position"))?;
+        ensure!(
+            !article_id.is_range(),
+            "Article ranges are not supported in simple block amendments"
+        );
+        let article_id = article_id.first_in_range();
+        let article = act
+            .article_mut(article_id)
+            .ok_or_else(|| anyhow!("Could not find article {article_id}"))?;
+        let last_part = self.position.get_last_part();
+        match &self.content {
+            BlockAmendmentChildren::Paragraph(content) => {
+                let range = last_part
+                    .paragraph()
+                    .ok_or_else(|| anyhow!("Position is not a paragraph"))?;
+                modify_multiple(
+                    &mut article.children,
+                    IdentifierRange::from_range(
+                        range.first_in_range().into(),
+                        range.last_in_range().into(),
+                    ),
+                    content,
+                    self.pure_insertion,
+                    change,
+                )
+            }
+            BlockAmendmentChildren::AlphabeticPoint(content) => {
+                let range = last_part
+                    .alphabetic_point()
+                    .ok_or_else(|| anyhow!("Position is not an alphabetic point"))?;
+                let paragraph = find_paragraph(article, self.position.paragraph())?;
+                match &mut paragraph.body {
+                    SAEBody::Children {
+                        children: ParagraphChildren::AlphabeticPoint(points),
+                        ..
+                    } => modify_multiple(points, range, content, self.pure_insertion, change),
+                    _ => bail!("Paragraph does not have alphabetic points"),
+                }
+            }
+            BlockAmendmentChildren::NumericPoint(content) => {
+                let range = last_part
+                    .numeric_point()
+                    .ok_or_else(|| anyhow!("Position is not a numeric point"))?;
+                let paragraph = find_paragraph(article, self.position.paragraph())?;
+                match &mut paragraph.body {
+                    SAEBody::Children {
+                        children: ParagraphChildren::NumericPoint(points),
+                        ..
+                    } => modify_multiple(points, range, content, self.pure_insertion, change),
+                    _ => bail!("Paragraph does not have numeric points"),
+                }
+            }
+            BlockAmendmentChildren::AlphabeticSubpoint(content) => {
+                let range = last_part
+                    .alphabetic_subpoint()
+                    .ok_or_else(|| anyhow!("Position is not an alphabetic subpoint"))?;
+                let paragraph = find_paragraph(article, self.position.paragraph())?;
+                let subpoints = match self.position.point() {
+                    Some(RefPartPoint::Alphabetic(point_id)) => {
+                        match &mut find_alphabetic_point(paragraph, point_id)?.body {
+                            SAEBody::Children {
+                                children: AlphabeticPointChildren::AlphabeticSubpoint(subpoints),
+                                ..
+                            } => subpoints,
+                            _ => bail!("Point does not have alphabetic subpoints"),
+                        }
+                    }
+                    Some(RefPartPoint::Numeric(point_id)) => {
+                        match &mut find_numeric_point(paragraph, point_id)?.body {
+                            SAEBody::Children {
+                                children: NumericPointChildren::AlphabeticSubpoint(subpoints),
+                                ..
+                            } => subpoints,
+                            _ => bail!("Point does not have alphabetic subpoints"),
+                        }
+                    }
+                    None => bail!("No point in subpoint amendment position"),
+                };
+                modify_multiple(subpoints, range, content, self.pure_insertion, change)
+            }
+            BlockAmendmentChildren::NumericSubpoint(content) => {
+                let range = last_part
+                    .numeric_subpoint()
+                    .ok_or_else(|| anyhow!("Position is not a numeric subpoint"))?;
+                let paragraph = find_paragraph(article, self.position.paragraph())?;
+                let point_id = match self.position.point() {
+                    Some(RefPartPoint::Alphabetic(point_id)) => point_id,
+                    _ => bail!("Numeric subpoints can only be inside alphabetic points"),
+                };
+                match &mut find_alphabetic_point(paragraph, point_id)?.body {
+                    SAEBody::Children {
+                        children: AlphabeticPointChildren::NumericSubpoint(subpoints),
+                        ..
+                    } => modify_multiple(subpoints, range, content, self.pure_insertion, change),
+                    _ => bail!("Point does not have numeric subpoints"),
+                }
+            }
+        }
+    }
+
+    fn affected_act(&self) -> Option<ActIdentifier> {
+        self.position.act()
+    }
+}
+
+/// Replace the elements in `range` with `content`, or insert `content` into
+/// its proper place if it's a pure insertion.
+fn modify_multiple<IT, CT>(
+    elements: &mut Vec<SubArticleElement<IT, CT>>,
+    range: IdentifierRange<IT>,
+    content: &[SubArticleElement<IT, CT>],
+    pure_insertion: bool,
+    change: &LastChange,
+) -> Result<()>
+where
+    IT: IdentifierCommon,
+    CT: ChildrenCommon,
+{
+    let (content_first, content_last) = match (content.first(), content.last()) {
+        (Some(first), Some(last)) => (first.identifier, last.identifier),
+        _ => bail!("Empty block amendment content"),
+    };
+    // The content may contain elements outside the range, e.g. when the
+    // amendment says "(3) bekezdés helyébe", but also adds a (3a) bekezdés.
+    let range = IdentifierRange::from_range(
+        range.first_in_range().min(content_first),
+        range.last_in_range().max(content_last),
+    );
+    let existing_count = elements
+        .iter()
+        .filter(|e| range.contains(e.identifier))
+        .count();
+    if pure_insertion {
+        ensure!(
+            existing_count == 0,
+            "Element with id {:?} already exists, but the amendment is a pure insertion",
+            range.first_in_range()
+        );
+    } else {
+        ensure!(
+            existing_count > 0,
+            "Could not find element with id {:?} to replace",
+            range.first_in_range()
+        );
+    }
+    elements.retain(|e| !range.contains(e.identifier));
+    let insertion_point = elements
+        .iter()
+        .position(|e| e.identifier > range.first_in_range())
+        .unwrap_or(elements.len());
+    elements.splice(
+        insertion_point..insertion_point,
+        content.iter().map(|e| SubArticleElement {
+            last_change: Some(change.clone()),
+            ..e.clone()
+        }),
+    );
+    Ok(())
+}
+
+fn find_paragraph(
+    article: &mut Article,
+    paragraph_id: Option<RefPartParagraph>,
+) -> Result<&mut Paragraph> {
+    let paragraph_id: ParagraphIdentifier = match paragraph_id {
+        Some(id) => {
+            ensure!(!id.is_range(), "Paragraph ranges are not allowed here");
+            id.first_in_range().into()
+        }
+        None => Default::default(),
+    };
+    article
+        .children
+        .iter_mut()
+        .find(|p| p.identifier == paragraph_id)
+        .ok_or_else(|| anyhow!("Could not find paragraph {paragraph_id:?}"))
+}
+
+fn find_alphabetic_point(
+    paragraph: &mut Paragraph,
+    point_id: IdentifierRange<AlphabeticIdentifier>,
+) -> Result<&mut AlphabeticPoint> {
+    ensure!(!point_id.is_range(), "Point ranges are not allowed here");
+    if let SAEBody::Children {
+        children: ParagraphChildren::AlphabeticPoint(points),
+        ..
+    } = &mut paragraph.body
+    {
+        points
+            .iter_mut()
+            .find(|p| p.identifier == point_id.first_in_range())
+            .ok_or_else(|| anyhow!("Could not find point {:?}", point_id.first_in_range()))
+    } else {
+        Err(anyhow!("Paragraph does not have alphabetic points"))
+    }
+}
+
+fn find_numeric_point(
+    paragraph: &mut Paragraph,
+    point_id: IdentifierRange<NumericIdentifier>,
+) -> Result<&mut NumericPoint> {
+    ensure!(!point_id.is_range(), "Point ranges are not allowed here");
+    if let SAEBody::Children {
+        children: ParagraphChildren::NumericPoint(points),
+        ..
+    } = &mut paragraph.body
+    {
+        points
+            .iter_mut()
+            .find(|p| p.identifier == point_id.first_in_range())
+            .ok_or_else(|| anyhow!("Could not find point {:?}", point_id.first_in_range()))
+    } else {
+        Err(anyhow!("Paragraph does not have numeric points"))
+    }
+}
@@ -0,0 +1,317 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{anyhow, ensure, Result};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::structural_amendment::{find_existing, find_parent_range};
+use crate::{
+    identifier::{IdentifierCommon, NumericIdentifier},
+    reference::{
+        attachment::AttachmentReference, structural::StructuralReference,
+        to_element::ReferenceToElement, Reference,
+    },
+    semantic_info::{EnforcementDate, EnforcementDateType, SpecialPhrase},
+    structure::{Act, ActChild, ChildrenCommon, SubArticleElement},
+    util::walker::{SAEVisitor, WalkSAE},
+};
+
+/// The time period when an element is in force
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EffectivePeriod {
+    /// The first day the element is in force.
+    pub from: NaiveDate,
+    /// The first day the element is no longer in force, if it has an inline repeal
+    /// ("...-én hatályát veszti").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repealed_on: Option<NaiveDate>,
+}
+
+impl EffectivePeriod {
+    /// Whether the element is in force on `date`. The period is half-open: the
+    /// element is not in force on the day of its repeal.
+    pub fn is_in_force_on(&self, date: NaiveDate) -> bool {
+        self.from <= date && !matches!(self.repealed_on, Some(repealed_on) if repealed_on <= date)
+    }
+}
+
+/// The resolved enforcement dates of an act
+#[derive(Debug, Clone)]
+pub struct EnforcementDateSet {
+    default_period: EffectivePeriod,
+    specific_periods: Vec<SpecificEffectivePeriod>,
+}
+
+#[derive(Debug, Clone)]
+struct SpecificEffectivePeriod {
+    positions: Vec<Reference>,
+    attachments: Vec<AttachmentReference>,
+    period: EffectivePeriod,
+}
+
+impl EnforcementDateSet {
+    /// Collect the enforcement date special phrases of an act with semantic info.
+    pub fn from_act(act: &Act) -> Result<Self> {
+        let mut visitor = EnforcementDateAccumulator::default();
+        act.walk_saes(&mut visitor)?;
+        let mut default_period = None;
+        let mut specific_periods = Vec::new();
+        for (position, ed) in visitor.result {
+            let period = EffectivePeriod {
+                from: ed.date.resolve(act.publication_date)?,
+                repealed_on: ed.inline_repeal,
+            };
+            if ed.is_default {
+                ensure!(
+                    default_period.is_none(),
+                    "Multiple default enforcement dates found"
+                );
+                default_period = Some(period);
+            } else {
+                // Positions are relative to the element containing the enforcement date
+                let mut positions = ed
+                    .positions
+                    .iter()
+                    .map(|p| Ok(p.relative_to(&position)?.without_act()))
+                    .collect::<Result<Vec<_>>>()?;
+                for structural_position in &ed.structural_positions {
+                    positions.extend(articles_in_structural_position(act, structural_position)?);
+                }
+                specific_periods.push(SpecificEffectivePeriod {
+                    positions,
+                    attachments: ed.attachment_positions.clone(),
+                    period,
+                });
+            }
+        }
+        Ok(Self {
+            default_period: default_period
+                .ok_or_else(|| anyhow!("No default enforcement date found"))?,
+            specific_periods,
+        })
+    }
+
+    /// The date when the element at `position` comes into force.
+    /// `position` may or may not contain the act identifier.
+    pub fn get_enforcement_date(&self, position: &Reference) -> NaiveDate {
+        self.get_effective_period(position).from
+    }
+
+    /// The period when the element at `position` is in force.
+    /// `position` may or may not contain the act identifier.
+    ///
+    /// Specific positions override the default, and the most specific position wins.
+    /// In case of identical positions, the later one takes precedence.
+    pub fn get_effective_period(&self, position: &Reference) -> EffectivePeriod {
+        let position = position.without_act();
+        let mut best: Option<(&Reference, EffectivePeriod)> = None;
+        for sep in &self.specific_periods {
+            for sep_position in &sep.positions {
+                if !sep_position.contains(&position) {
+                    continue;
+                }
+                if let Some((best_position, _)) = best {
+                    if !best_position.contains(sep_position) {
+                        continue;
+                    }
+                }
+                best = Some((sep_position, sep.period));
+            }
+        }
+        best.map_or(self.default_period, |(_, period)| period)
+    }
+
+    /// The period when the attachment with the identifier `attachment` is in force.
+    ///
+    /// In case of multiple matching enforcement dates, the later one takes precedence.
+    pub fn get_attachment_effective_period(
+        &self,
+        attachment: NumericIdentifier,
+    ) -> EffectivePeriod {
+        self.specific_periods
+            .iter()
+            .rev()
+            .find(|sep| sep.attachments.iter().any(|a| a.contains(attachment)))
+            .map_or(self.default_period, |sep| sep.period)
+    }
+}
+
+impl Act {
+    /// Calculate the effective period of every article and SAE in the act, in
+    /// document order.
+    ///
+    /// Requires semantic info to be present.
+    pub fn effective_periods(&self) -> Result<Vec<(Reference, EffectivePeriod)>> {
+        let enforcement_dates = EnforcementDateSet::from_act(self)?;
+        let mut visitor = PositionAccumulator::default();
+        let act_reference = self.reference();
+        for article in self.articles() {
+            let article_reference = article.reference().relative_to(&act_reference)?;
+            visitor.result.push(article_reference.clone());
+            article
+                .children
+                .walk_saes(&article_reference, &mut visitor)?;
+        }
+        Ok(visitor
+            .result
+            .into_iter()
+            .map(|position| {
+                let period = enforcement_dates.get_effective_period(&position);
+                (position, period)
+            })
+            .collect())
+    }
+}
+
+fn articles_in_structural_position(
+    act: &Act,
+    position: &StructuralReference,
+) -> Result<Vec<Reference>> {
+    let within = find_parent_range(&act.children, position)?;
+    let range = find_existing(&act.children, within, &position.structural_element, &[])?;
+    Ok(act.children[range]
+        .iter()
+        .filter_map(|child| match child {
+            ActChild::Article(article) => Some(article.reference()),
+            _ => None,
+        })
+        .collect())
+}
+
+#[derive(Debug, Default)]
+struct EnforcementDateAccumulator {
+    result: Vec<(Reference, EnforcementDate)>,
+}
+
+impl SAEVisitor for EnforcementDateAccumulator {
+    fn on_enter<IT: IdentifierCommon, CT: ChildrenCommon>(
+        &mut self,
+        position: &Reference,
+        element: &SubArticleElement<IT, CT>,
+    ) -> Result<()> {
+        if let Some(SpecialPhrase::EnforcementDate(ed)) = &element.semantic_info.special_phrase {
+            self.result.push((position.clone(), ed.clone()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct PositionAccumulator {
+    result: Vec<Reference>,
+}
+
+impl SAEVisitor for PositionAccumulator {
+    fn on_enter<IT: IdentifierCommon, CT: ChildrenCommon>(
+        &mut self,
+        position: &Reference,
+        _element: &SubArticleElement<IT, CT>,
+    ) -> Result<()> {
+        // Default paragraphs have the same reference as their article
+        if self.result.last() != Some(position) {
+            self.result.push(position.clone());
+        }
+        Ok(())
+    }
+}
+
+impl EnforcementDateType {
+    /// Calculate the actual date for an act published at `publication_date`.
+    pub fn resolve(&self, publication_date: NaiveDate) -> Result<NaiveDate> {
+        match self {
+            EnforcementDateType::Date(date) => Ok(*date),
+            EnforcementDateType::DaysAfterPublication(days) => {
+                Ok(publication_date + Duration::days((*days).into()))
+            }
+            EnforcementDateType::DayInMonthAfterPublication { month, day } => {
+                let months_after = month.unwrap_or(1) as i32;
+                let month_index =
+                    publication_date.year() * 12 + publication_date.month0() as i32 + months_after;
+                NaiveDate::from_ymd_opt(
+                    month_index.div_euclid(12),
+                    month_index.rem_euclid(12) as u32 + 1,
+                    (*day).into(),
+                )
+                .ok_or_else(|| {
+                    anyhow!("Invalid enforcement date: {self:?} after {publication_date}")
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_resolve() {
+        let publication_date = date(2012, 12, 20);
+        let cases = [
+            (
+                EnforcementDateType::Date(date(2013, 3, 4)),
+                date(2013, 3, 4),
+            ),
+            (
+                EnforcementDateType::DaysAfterPublication(1),
+                date(2012, 12, 21),
+            ),
+            (
+                EnforcementDateType::DaysAfterPublication(15),
+                date(2013, 1, 4),
+            ),
+            (
+                EnforcementDateType::DayInMonthAfterPublication {
+                    month: None,
+                    day: 1,
+                },
+                date(2013, 1, 1),
+            ),
+            (
+                EnforcementDateType::DayInMonthAfterPublication {
+                    month: Some(2),
+                    day: 15,
+                },
+                date(2013, 2, 15),
+            ),
+        ];
+        for (edt, expected) in cases {
+            assert_eq!(edt.resolve(publication_date).unwrap(), expected, "{edt:?}");
+        }
+    }
+
+    #[test]
+    fn test_is_in_force_on() {
+        let period = EffectivePeriod {
+            from: date(2013, 1, 1),
+            repealed_on: Some(date(2013, 12, 31)),
+        };
+        assert!(!period.is_in_force_on(date(2012, 12, 31)));
+        assert!(period.is_in_force_on(date(2013, 1, 1)));
+        assert!(period.is_in_force_on(date(2013, 12, 30)));
+        assert!(!period.is_in_force_on(date(2013, 12, 31)));
+        let period = EffectivePeriod {
+            from: date(2013, 1, 1),
+            repealed_on: None,
+        };
+        assert!(period.is_in_force_on(date(2100, 1, 1)));
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+
+use super::{
+    block_amendment::BlockAmendmentWithContent,
+    structural_amendment::StructuralBlockAmendmentWithContent, AppliableModification,
+};
+use crate::{
+    identifier::IdentifierCommon,
+    reference::{to_element::ReferenceToElement, Reference},
+    semantic_info::SpecialPhrase,
+    structure::{Act, ChildrenCommon, ParagraphChildren, SAEBody, SubArticleElement},
+    util::walker::{SAEVisitor, WalkSAE},
+};
+
+/// Collect all modifications from an act that has semantic info and converted block amendments.
+pub fn extract_modifications_from_act(act: &Act) -> Result<Vec<AppliableModification>> {
+    let mut extractor = ModificationExtractor::default();
+    let act_reference = act.reference();
+    for article in act.articles() {
+        let article_reference = article.reference().relative_to(&act_reference)?;
+        // Block amendments can only be found on the paragraph level, see Paragraph::convert_block_amendments
+        for paragraph in &article.children {
+            let cause = paragraph.reference().relative_to(&article_reference)?;
+            let result = &mut extractor.result;
+            match (&paragraph.semantic_info.special_phrase, &paragraph.body) {
+                (
+                    Some(SpecialPhrase::BlockAmendment(phrase)),
+                    SAEBody::Children {
+                        children: ParagraphChildren::BlockAmendment(content),
+                        ..
+                    },
+                ) => result.push(AppliableModification {
+                    cause,
+                    modification: BlockAmendmentWithContent {
+                        position: phrase.position.clone(),
+                        pure_insertion: phrase.pure_insertion,
+                        content: content.children.clone(),
+                    }
+                    .into(),
+                }),
+                (
+                    Some(SpecialPhrase::StructuralBlockAmendment(phrase)),
+                    SAEBody::Children {
+                        children: ParagraphChildren::StructuralBlockAmendment(content),
+                        ..
+                    },
+                ) => result.push(AppliableModification {
+                    cause,
+                    modification: StructuralBlockAmendmentWithContent {
+                        position: phrase.position.clone(),
+                        pure_insertion: phrase.pure_insertion,
+                        content: content.children.clone(),
+                    }
+                    .into(),
+                }),
+                _ => (),
+            }
+            paragraph.walk_saes(&article_reference, &mut extractor)?;
+        }
+    }
+    Ok(extractor.result)
+}
+
+/// Collects the modifications that can be found on any SAE level
+#[derive(Debug, Default)]
+struct ModificationExtractor {
+    result: Vec<AppliableModification>,
+}
+
+impl SAEVisitor for ModificationExtractor {
+    fn on_enter<IT: IdentifierCommon, CT: ChildrenCommon>(
+        &mut self,
+        position: &Reference,
+        element: &SubArticleElement<IT, CT>,
+    ) -> Result<()> {
+        match &element.semantic_info.special_phrase {
+            Some(SpecialPhrase::TextAmendment(text_amendments)) => {
+                for text_amendment in text_amendments {
+                    self.result.push(AppliableModification {
+                        cause: position.clone(),
+                        modification: text_amendment.clone().into(),
+                    });
+                }
+            }
+            Some(SpecialPhrase::Repeal(repeals)) => {
+                for repeal in repeals {
+                    self.result.push(AppliableModification {
+                        cause: position.clone(),
+                        modification: repeal.clone().into(),
+                    });
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+}
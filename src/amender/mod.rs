@@ -0,0 +1,117 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+pub mod auto_repeal;
+pub mod block_amendment;
+pub mod enforcement_date;
+pub mod extract;
+pub mod repeal;
+pub mod snapshot;
+pub mod structural_amendment;
+pub mod text_amendment;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::NaiveDate;
+use from_variants::FromVariants;
+use serde::{Deserialize, Serialize};
+
+use self::{
+    block_amendment::BlockAmendmentWithContent, extract::extract_modifications_from_act,
+    structural_amendment::StructuralBlockAmendmentWithContent,
+};
+use crate::{
+    identifier::ActIdentifier,
+    reference::Reference,
+    semantic_info::{RepealReference, TextAmendment},
+    structure::{Act, ChangeCause, LastChange},
+};
+
+pub trait ModifyAct {
+    /// Apply the modification to the act, and mark every changed element with `change`
+    fn apply(&self, act: &mut Act, change: &LastChange) -> Result<()>;
+
+    /// The act which is modified by this modification, if known
+    fn affected_act(&self) -> Option<ActIdentifier>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromVariants)]
+pub enum AppliableModificationType {
+    BlockAmendment(BlockAmendmentWithContent),
+    StructuralBlockAmendment(StructuralBlockAmendmentWithContent),
+    TextAmendment(TextAmendment),
+    Repeal(RepealReference),
+}
+
+/// A modification of an act that was extracted from an amending act,
+/// and can be directly applied to the target act.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppliableModification {
+    /// The provision in the amending act that contained the modification
+    pub cause: Reference,
+    pub modification: AppliableModificationType,
+}
+
+impl AppliableModification {
+    pub fn apply(&self, act: &mut Act, date: NaiveDate) -> Result<()> {
+        let change = LastChange {
+            date,
+            cause: ChangeCause::Amendment(self.cause.clone()),
+        };
+        self.modification.apply(act, &change)
+    }
+
+    pub fn affected_act(&self) -> Option<ActIdentifier> {
+        self.modification.affected_act()
+    }
+}
+
+impl ModifyAct for AppliableModificationType {
+    fn apply(&self, act: &mut Act, change: &LastChange) -> Result<()> {
+        match self {
+            AppliableModificationType::BlockAmendment(x) => x.apply(act, change),
+            AppliableModificationType::StructuralBlockAmendment(x) => x.apply(act, change),
+            AppliableModificationType::TextAmendment(x) => x.apply(act, change),
+            AppliableModificationType::Repeal(x) => x.apply(act, change),
+        }
+    }
+
+    fn affected_act(&self) -> Option<ActIdentifier> {
+        match self {
+            AppliableModificationType::BlockAmendment(x) => x.affected_act(),
+            AppliableModificationType::StructuralBlockAmendment(x) => x.affected_act(),
+            AppliableModificationType::TextAmendment(x) => x.affected_act(),
+            AppliableModificationType::Repeal(x) => x.affected_act(),
+        }
+    }
+}
+
+impl Act {
+    /// Apply all modifications in `amending_act` that target this act.
+    ///
+    /// The modified elements will be marked with a `LastChange` of `date`.
+    /// Semantic info is not added to the new elements: call `add_semantic_info` after
+    /// all modifications were applied.
+    pub fn apply_amendments_from(&mut self, amending_act: &Act, date: NaiveDate) -> Result<()> {
+        for modification in extract_modifications_from_act(amending_act)? {
+            if modification.affected_act() == Some(self.identifier) {
+                modification.apply(self, date).with_context(|| {
+                    anyhow!("Could not apply amendment at {:?}", modification.cause)
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{anyhow, ensure, Result};
+
+use super::{
+    structural_amendment::{find_existing, find_parent_range, set_last_change},
+    ModifyAct,
+};
+use crate::{
+    identifier::{ActIdentifier, IdentifierCommon},
+    reference::{structural::StructuralReference, Reference},
+    semantic_info::{RepealReference, SemanticInfo},
+    structure::{
+        Act, ActChild, Article, ChildrenCommon, LastChange, Paragraph, SAEBody, SubArticleElement,
+    },
+    util::walker::SAEVisitorMut,
+};
+
+impl ModifyAct for RepealReference {
+    fn apply(&self, act: &mut Act, change: &LastChange) -> Result<()> {
+        match self {
+            RepealReference::Reference(reference) => repeal_reference(act, reference, change),
+            RepealReference::StructuralReference(position) => {
+                repeal_structural_reference(act, position, change)
+            }
+        }
+    }
+
+    fn affected_act(&self) -> Option<ActIdentifier> {
+        match self {
+            RepealReference::Reference(reference) => reference.act(),
+            RepealReference::StructuralReference(position) => position.act,
+        }
+    }
+}
+
+fn repeal_reference(act: &mut Act, reference: &Reference, change: &LastChange) -> Result<()> {
+    let reference = reference.without_act();
+    if reference.paragraph().is_none() && reference.point().is_none() {
+        let article_range = reference
+            .article()
+            .ok_or_else(|| anyhow!("Repeal reference does not contain an article"))?;
+        let mut repealed_count = 0;
+        for article in act.articles_mut() {
+            if article_range.contains(article.identifier) {
+                repeal_article(article, change);
+                repealed_count += 1;
+            }
+        }
+        ensure!(
+            repealed_count > 0,
+            "Could not find article {article_range:?}"
+        );
+    } else {
+        let mut visitor = RepealingVisitor {
+            reference: &reference,
+            change,
+            repealed_count: 0,
+        };
+        act.walk_saes_mut(&mut visitor)?;
+        ensure!(
+            visitor.repealed_count > 0,
+            "Could not find element {reference:?} to repeal"
+        );
+    }
+    Ok(())
+}
+
+fn repeal_structural_reference(
+    act: &mut Act,
+    position: &StructuralReference,
+    change: &LastChange,
+) -> Result<()> {
+    let within = find_parent_range(&act.children, position)?;
+    let mut range = find_existing(&act.children, within, &position.structural_element, &[])?;
+    if position.title_only {
+        range.end = range.start + 1;
+    }
+    for child in &mut act.children[range] {
+        match child {
+            ActChild::StructuralElement(se) => se.title = String::new(),
+            ActChild::Subtitle(st) => st.title = String::new(),
+            ActChild::Article(article) => repeal_article(article, change),
+        }
+        set_last_change(child, change);
+    }
+    Ok(())
+}
+
+/// Repealed articles keep their identifier, but all their contents are removed.
+pub(super) fn repeal_article(article: &mut Article, change: &LastChange) {
+    article.title = None;
+    article.children = vec![Paragraph {
+        identifier: Default::default(),
+        body: SAEBody::Text(String::new()),
+        semantic_info: Default::default(),
+        last_change: None,
+    }];
+    article.last_change = Some(change.clone());
+}
+
+struct RepealingVisitor<'a> {
+    reference: &'a Reference,
+    change: &'a LastChange,
+    repealed_count: usize,
+}
+
+impl<'a> SAEVisitorMut for RepealingVisitor<'a> {
+    fn on_enter<IT: IdentifierCommon, CT: ChildrenCommon>(
+        &mut self,
+        position: &Reference,
+        element: &mut SubArticleElement<IT, CT>,
+    ) -> Result<()> {
+        let position = position.without_act();
+        // Only repeal the topmost referenced elements: their children will be
+        // removed anyway.
+        if self.reference.contains(&position) && !self.reference.contains(&position.parent()) {
+            element.body = SAEBody::Text(String::new());
+            element.semantic_info = SemanticInfo::default();
+            element.last_change = Some(self.change.clone());
+            self.repealed_count += 1;
+        }
+        Ok(())
+    }
+}
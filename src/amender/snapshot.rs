@@ -0,0 +1,290 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{anyhow, ensure, Context, Result};
+use chrono::NaiveDate;
+use log::warn;
+
+use super::{
+    enforcement_date::EnforcementDateSet, extract::extract_modifications_from_act,
+    repeal::repeal_article, AppliableModification,
+};
+use crate::{
+    identifier::IdentifierCommon,
+    reference::{to_element::ReferenceToElement, Reference},
+    semantic_info::SemanticInfo,
+    structure::{
+        Act, ActChild, AlphabeticPointChildren, AlphabeticSubpointChildren, ChangeCause,
+        ChildrenCommon, LastChange, NumericPointChildren, NumericSubpointChildren,
+        ParagraphChildren, SAEBody, SubArticleElement,
+    },
+};
+
+impl Act {
+    /// The state of the act on `date` ("időállapot").
+    ///
+    /// All modifications targeting this act in `corpus` that are in force on `date`
+    /// are applied in the order of their enforcement dates. Modifications with the same
+    /// enforcement date are applied in the order of the amending acts' publication,
+    /// and then in document order. Amending acts with unresolvable enforcement dates
+    /// are skipped with a warning.
+    ///
+    /// Original provisions of the act that are not yet in force on `date` are removed,
+    /// and the ones that were repealed by the act itself are replaced by empty placeholders.
+    /// Amending provisions in the act itself that are no longer in force are auto-repealed.
+    ///
+    /// Acts in the corpus must have semantic info and converted block amendments.
+    pub fn state_at<'a>(
+        &self,
+        date: NaiveDate,
+        corpus: impl IntoIterator<Item = &'a Act>,
+    ) -> Result<Act> {
+        ensure!(
+            date >= self.publication_date,
+            "Act {} was not yet published on {date}",
+            self.identifier
+        );
+        let mut modifications = Vec::new();
+        for amending_act in corpus {
+            if amending_act.identifier == self.identifier || amending_act.publication_date > date {
+                continue;
+            }
+            let relevant_modifications: Vec<AppliableModification> =
+                extract_modifications_from_act(amending_act)?
+                    .into_iter()
+                    .filter(|m| m.affected_act() == Some(self.identifier))
+                    .collect();
+            if relevant_modifications.is_empty() {
+                continue;
+            }
+            let enforcement_dates = match EnforcementDateSet::from_act(amending_act) {
+                Ok(enforcement_dates) => enforcement_dates,
+                Err(error) => {
+                    warn!(
+                        "Skipping amendments of {}: invalid enforcement dates: {error:?}",
+                        amending_act.identifier
+                    );
+                    continue;
+                }
+            };
+            for modification in relevant_modifications {
+                let enforcement_date = enforcement_dates.get_enforcement_date(&modification.cause);
+                if enforcement_date <= date {
+                    modifications.push((
+                        enforcement_date,
+                        amending_act.publication_date,
+                        amending_act.identifier,
+                        modification,
+                    ));
+                }
+            }
+        }
+        // Stable sort, so document order is kept within the same act.
+        modifications.sort_by_key(|(enforcement_date, publication_date, identifier, _)| {
+            (*enforcement_date, *publication_date, *identifier)
+        });
+
+        let mut result = self.clone();
+        for (enforcement_date, _, _, modification) in modifications {
+            modification
+                .apply(&mut result, enforcement_date)
+                .with_context(|| {
+                    anyhow!("Could not apply amendment at {:?}", modification.cause)
+                })?;
+        }
+        match EnforcementDateSet::from_act(self) {
+            Ok(enforcement_dates) => {
+                result.remove_provisions_not_in_force(&enforcement_dates, date)?;
+                result.apply_auto_repeals_with(&enforcement_dates, date)?;
+            }
+            Err(error) => warn!(
+                "Not checking the effective periods of {}: invalid enforcement dates: {error:?}",
+                self.identifier
+            ),
+        }
+        Ok(result)
+    }
+
+    /// Remove the original provisions that are not yet in force on `date`, and repeal
+    /// the ones with an inline repeal. Changed elements are governed by the amending act,
+    /// so they are kept as they are.
+    fn remove_provisions_not_in_force(
+        &mut self,
+        enforcement_dates: &EnforcementDateSet,
+        date: NaiveDate,
+    ) -> Result<()> {
+        let act_reference = self.reference();
+        let mut children = Vec::with_capacity(self.children.len());
+        for mut child in std::mem::take(&mut self.children) {
+            if let ActChild::Article(article) = &mut child {
+                if article.last_change.is_none() {
+                    let reference = article.reference().relative_to(&act_reference)?;
+                    let period = enforcement_dates.get_effective_period(&reference);
+                    if period.from > date {
+                        continue;
+                    }
+                    match period.repealed_on {
+                        Some(repealed_on) if repealed_on <= date => {
+                            repeal_article(article, &inline_repeal(repealed_on))
+                        }
+                        _ => article.children.remove_not_in_force(
+                            &reference,
+                            enforcement_dates,
+                            date,
+                        )?,
+                    }
+                }
+            }
+            children.push(child);
+        }
+        self.children = children;
+        Ok(())
+    }
+}
+
+fn inline_repeal(date: NaiveDate) -> LastChange {
+    LastChange {
+        date,
+        cause: ChangeCause::InlineRepeal,
+    }
+}
+
+trait RemoveNotInForce {
+    fn remove_not_in_force(
+        &mut self,
+        base: &Reference,
+        enforcement_dates: &EnforcementDateSet,
+        date: NaiveDate,
+    ) -> Result<()>;
+}
+
+impl<IT, CT> RemoveNotInForce for Vec<SubArticleElement<IT, CT>>
+where
+    SubArticleElement<IT, CT>: ReferenceToElement,
+    IT: IdentifierCommon,
+    CT: ChildrenCommon + RemoveNotInForce,
+{
+    fn remove_not_in_force(
+        &mut self,
+        base: &Reference,
+        enforcement_dates: &EnforcementDateSet,
+        date: NaiveDate,
+    ) -> Result<()> {
+        let mut result = Vec::with_capacity(self.len());
+        for mut element in std::mem::take(self) {
+            if element.last_change.is_none() {
+                let reference = element.reference().relative_to(base)?;
+                let period = enforcement_dates.get_effective_period(&reference);
+                if period.from > date {
+                    continue;
+                }
+                match period.repealed_on {
+                    Some(repealed_on) if repealed_on <= date => {
+                        element.body = SAEBody::Text(String::new());
+                        element.semantic_info = SemanticInfo::default();
+                        element.last_change = Some(inline_repeal(repealed_on));
+                    }
+                    _ => {
+                        if let SAEBody::Children { children, .. } = &mut element.body {
+                            children.remove_not_in_force(&reference, enforcement_dates, date)?;
+                        }
+                    }
+                }
+            }
+            result.push(element);
+        }
+        *self = result;
+        Ok(())
+    }
+}
+
+impl RemoveNotInForce for ParagraphChildren {
+    fn remove_not_in_force(
+        &mut self,
+        base: &Reference,
+        enforcement_dates: &EnforcementDateSet,
+        date: NaiveDate,
+    ) -> Result<()> {
+        match self {
+            ParagraphChildren::AlphabeticPoint(x) => {
+                x.remove_not_in_force(base, enforcement_dates, date)
+            }
+            ParagraphChildren::NumericPoint(x) => {
+                x.remove_not_in_force(base, enforcement_dates, date)
+            }
+            ParagraphChildren::QuotedBlock(_)
+            | ParagraphChildren::BlockAmendment(_)
+            | ParagraphChildren::StructuralBlockAmendment(_) => Ok(()),
+        }
+    }
+}
+
+impl RemoveNotInForce for AlphabeticPointChildren {
+    fn remove_not_in_force(
+        &mut self,
+        base: &Reference,
+        enforcement_dates: &EnforcementDateSet,
+        date: NaiveDate,
+    ) -> Result<()> {
+        match self {
+            AlphabeticPointChildren::AlphabeticSubpoint(x) => {
+                x.remove_not_in_force(base, enforcement_dates, date)
+            }
+            AlphabeticPointChildren::NumericSubpoint(x) => {
+                x.remove_not_in_force(base, enforcement_dates, date)
+            }
+        }
+    }
+}
+
+impl RemoveNotInForce for NumericPointChildren {
+    fn remove_not_in_force(
+        &mut self,
+        base: &Reference,
+        enforcement_dates: &EnforcementDateSet,
+        date: NaiveDate,
+    ) -> Result<()> {
+        match self {
+            NumericPointChildren::AlphabeticSubpoint(x) => {
+                x.remove_not_in_force(base, enforcement_dates, date)
+            }
+        }
+    }
+}
+
+impl RemoveNotInForce for AlphabeticSubpointChildren {
+    fn remove_not_in_force(
+        &mut self,
+        _base: &Reference,
+        _enforcement_dates: &EnforcementDateSet,
+        _date: NaiveDate,
+    ) -> Result<()> {
+        // This is an empty enum, the function shall never run.
+        match *self {}
+    }
+}
+
+impl RemoveNotInForce for NumericSubpointChildren {
+    fn remove_not_in_force(
+        &mut self,
+        _base: &Reference,
+        _enforcement_dates: &EnforcementDateSet,
+        _date: NaiveDate,
+    ) -> Result<()> {
+        // This is an empty enum, the function shall never run.
+        match *self {}
+    }
+}
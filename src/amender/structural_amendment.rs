@@ -0,0 +1,450 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::ops::Range;
+
+use anyhow::{anyhow, bail, ensure, Result};
+use serde::{Deserialize, Serialize};
+
+use super::ModifyAct;
+use crate::{
+    identifier::{ActIdentifier, ArticleIdentifier, NumericIdentifier},
+    reference::structural::{StructuralReference, StructuralReferenceElement},
+    structure::{Act, ActChild, LastChange, StructuralElementType, Subtitle},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructuralBlockAmendmentWithContent {
+    pub position: StructuralReference,
+    pub pure_insertion: bool,
+    pub content: Vec<ActChild>,
+}
+
+impl ModifyAct for StructuralBlockAmendmentWithContent {
+    fn apply(&self, act: &mut Act, change: &LastChange) -> Result<()> {
+        ensure!(
+            !self.content.is_empty(),
+            "Empty structural amendment content"
+        );
+        let children = &act.children;
+        let within = find_parent_range(children, &self.position)?;
+        let range = if self.pure_insertion {
+            let insertion_point = find_insertion_point(
+                children,
+                within,
+                &self.position.structural_element,
+                &self.content,
+            )?;
+            insertion_point..insertion_point
+        } else if self.position.title_only {
+            let range = find_existing(children, within, &self.position.structural_element, &[])?;
+            range.start..range.start + 1
+        } else {
+            find_existing(
+                children,
+                within,
+                &self.position.structural_element,
+                &self.content,
+            )?
+        };
+        let mut replacement: Vec<ActChild> = self
+            .content
+            .iter()
+            .map(|child| {
+                let mut child = child.clone();
+                set_last_change(&mut child, change);
+                child
+            })
+            .collect();
+        if !self.pure_insertion
+            && matches!(
+                self.position.structural_element,
+                StructuralReferenceElement::Article(_)
+            )
+        {
+            replacement = keep_unreplaced_children(&act.children[range.clone()], replacement)?;
+        }
+        act.children.splice(range, replacement);
+        Ok(())
+    }
+
+    fn affected_act(&self) -> Option<ActIdentifier> {
+        self.position.act
+    }
+}
+
+/// The range of children that contains the referenced element, based on the book and parent
+/// fields. The headers of the parent elements are not part of the range.
+pub(crate) fn find_parent_range(
+    children: &[ActChild],
+    position: &StructuralReference,
+) -> Result<Range<usize>> {
+    let mut within = 0..children.len();
+    if let Some(book_id) = position.book {
+        within = find_structural_element(children, within, StructuralElementType::Book, book_id)?;
+        within.start += 1;
+    }
+    if let Some(parent) = &position.parent {
+        within = find_existing(children, within, &parent.clone().into(), &[])?;
+        within.start += 1;
+    }
+    Ok(within)
+}
+
+pub(super) fn set_last_change(child: &mut ActChild, change: &LastChange) {
+    let last_change = match child {
+        ActChild::StructuralElement(x) => &mut x.last_change,
+        ActChild::Subtitle(x) => &mut x.last_change,
+        ActChild::Article(x) => &mut x.last_change,
+    };
+    *last_change = Some(change.clone());
+}
+
+/// Find the range of children that is referred to by `element`.
+///
+/// In case of articles, the range is extended to all the articles in `content`,
+/// so that multiple article replacements work even if the position only contains
+/// the first article.
+pub(crate) fn find_existing(
+    children: &[ActChild],
+    within: Range<usize>,
+    element: &StructuralReferenceElement,
+    content: &[ActChild],
+) -> Result<Range<usize>> {
+    Ok(match element {
+        StructuralReferenceElement::Part(id) => find_structural_element(
+            children,
+            within,
+            StructuralElementType::Part { is_special: false },
+            *id,
+        )?,
+        StructuralReferenceElement::Title(id) => {
+            find_structural_element(children, within, StructuralElementType::Title, *id)?
+        }
+        StructuralReferenceElement::Chapter(id) => {
+            find_structural_element(children, within, StructuralElementType::Chapter, *id)?
+        }
+        StructuralReferenceElement::SubtitleId(id) => {
+            let start = find_subtitle(children, within.clone(), |st| st.identifier == Some(*id))?;
+            start..end_of_subtitle(children, within, start)
+        }
+        StructuralReferenceElement::SubtitleRange(range) => {
+            let start = find_subtitle(children, within.clone(), |st| {
+                st.identifier == Some(range.first_in_range())
+            })?;
+            let last = find_subtitle(children, within.clone(), |st| {
+                st.identifier == Some(range.last_in_range())
+            })?;
+            start..end_of_subtitle(children, within, last)
+        }
+        StructuralReferenceElement::SubtitleTitle(title) => {
+            let start = find_subtitle(children, within.clone(), |st| &st.title == title)?;
+            start..end_of_subtitle(children, within, start)
+        }
+        StructuralReferenceElement::SubtitleAfterArticle(article_id) => {
+            let article_index = find_article(children, within, *article_id)?;
+            let subtitle_index = article_index + 1;
+            ensure!(
+                matches!(children.get(subtitle_index), Some(ActChild::Subtitle(_))),
+                "There is no subtitle after article {article_id}"
+            );
+            subtitle_index..subtitle_index + 1
+        }
+        StructuralReferenceElement::SubtitleBeforeArticle(article_id) => {
+            let subtitle_index = find_subtitle_before_article(children, within, *article_id)?;
+            subtitle_index..subtitle_index + 1
+        }
+        StructuralReferenceElement::SubtitleBeforeArticleInclusive(article_id) => {
+            let subtitle_index =
+                find_subtitle_before_article(children, within.clone(), *article_id)?;
+            let subtitle_end = end_of_subtitle(children, within, subtitle_index);
+            let last_article = last_article_id(content).unwrap_or(*article_id);
+            let end = children[subtitle_index..subtitle_end]
+                .iter()
+                .rposition(|c| matches!(c, ActChild::Article(a) if a.identifier <= last_article))
+                .map_or(subtitle_end, |i| subtitle_index + i + 1);
+            subtitle_index..end
+        }
+        StructuralReferenceElement::SubtitleUnknown => {
+            bail!("Unknown subtitle positions can only be used for insertions")
+        }
+        StructuralReferenceElement::Article(range) => {
+            let first = first_article_id(content)
+                .map_or(range.first_in_range(), |id| id.min(range.first_in_range()));
+            let last = last_article_id(content)
+                .map_or(range.last_in_range(), |id| id.max(range.last_in_range()));
+            let is_in_range = |c: &ActChild| matches!(c, ActChild::Article(a) if a.identifier >= first && a.identifier <= last);
+            let start = children[within.clone()]
+                .iter()
+                .position(is_in_range)
+                .ok_or_else(|| anyhow!("Could not find article {first}"))?;
+            let end = children[within.clone()]
+                .iter()
+                .rposition(is_in_range)
+                .ok_or_else(|| anyhow!("Could not find article {last}"))?;
+            within.start + start..within.start + end + 1
+        }
+    })
+}
+
+/// Article ranges may contain subtitles and structural elements between the articles.
+/// If `content` consists of articles only, these are kept in front of the first new
+/// article that comes after them. Otherwise all of them have to be replaced by `content`.
+fn keep_unreplaced_children(
+    existing: &[ActChild],
+    content: Vec<ActChild>,
+) -> Result<Vec<ActChild>> {
+    let non_articles: Vec<&ActChild> = existing
+        .iter()
+        .filter(|c| !matches!(c, ActChild::Article(_)))
+        .collect();
+    if non_articles.is_empty() {
+        return Ok(content);
+    }
+    if content.iter().any(|c| !matches!(c, ActChild::Article(_))) {
+        for non_article in non_articles {
+            ensure!(
+                content.iter().any(|c| is_replacement_of(c, non_article)),
+                "Article range amendment would remove {}",
+                non_article_description(non_article)
+            );
+        }
+        return Ok(content);
+    }
+    let mut result = Vec::with_capacity(existing.len() + content.len());
+    let mut content = content.into_iter().peekable();
+    for (index, child) in existing.iter().enumerate() {
+        if matches!(child, ActChild::Article(_)) {
+            continue;
+        }
+        // Article ranges always end with an article.
+        let next_article = first_article_id(&existing[index..])
+            .ok_or_else(|| anyhow!("Article range amendment ends in a non-article"))?;
+        while let Some(ActChild::Article(a)) = content.peek() {
+            if a.identifier >= next_article {
+                break;
+            }
+            result.extend(content.next());
+        }
+        result.push(child.clone());
+    }
+    result.extend(content);
+    Ok(result)
+}
+
+fn is_replacement_of(new: &ActChild, old: &ActChild) -> bool {
+    match (new, old) {
+        (ActChild::StructuralElement(new), ActChild::StructuralElement(old)) => {
+            structural_level(new.element_type) == structural_level(old.element_type)
+                && new.identifier == old.identifier
+        }
+        (ActChild::Subtitle(new), ActChild::Subtitle(old)) => match old.identifier {
+            Some(_) => new.identifier == old.identifier,
+            None => new.identifier.is_none() && new.title == old.title,
+        },
+        _ => false,
+    }
+}
+
+fn non_article_description(child: &ActChild) -> String {
+    match child {
+        ActChild::StructuralElement(se) => {
+            format!(
+                "structural element {:?} {:?}",
+                se.element_type, se.identifier
+            )
+        }
+        ActChild::Subtitle(st) => format!("subtitle {:?}", st.title),
+        ActChild::Article(a) => format!("article {}", a.identifier),
+    }
+}
+
+/// Find where new elements should be inserted for a pure insertion.
+fn find_insertion_point(
+    children: &[ActChild],
+    within: Range<usize>,
+    element: &StructuralReferenceElement,
+    content: &[ActChild],
+) -> Result<usize> {
+    match element {
+        StructuralReferenceElement::SubtitleBeforeArticle(article_id) => {
+            return find_article(children, within, *article_id);
+        }
+        StructuralReferenceElement::SubtitleAfterArticle(article_id) => {
+            return Ok(find_article(children, within, *article_id)? + 1);
+        }
+        _ => (),
+    }
+    if let Some(first_article) = first_article_id(content) {
+        // Insert before the first article that comes after the new ones,
+        // but also before its subtitle and structural elements.
+        let mut result = children[within.clone()]
+            .iter()
+            .position(|c| matches!(c, ActChild::Article(a) if a.identifier > first_article))
+            .map_or(within.end, |i| within.start + i);
+        while result > within.start && !matches!(children[result - 1], ActChild::Article(_)) {
+            result -= 1;
+        }
+        return Ok(result);
+    }
+    // No articles in the content, so we have to go by the identifier of the
+    // new element: put it after the previous element of the same type.
+    let previous = match element {
+        StructuralReferenceElement::Part(id) => rfind_structural_element_before(
+            children,
+            within.clone(),
+            StructuralElementType::Part { is_special: false },
+            *id,
+        ),
+        StructuralReferenceElement::Title(id) => rfind_structural_element_before(
+            children,
+            within.clone(),
+            StructuralElementType::Title,
+            *id,
+        ),
+        StructuralReferenceElement::Chapter(id) => rfind_structural_element_before(
+            children,
+            within.clone(),
+            StructuralElementType::Chapter,
+            *id,
+        ),
+        StructuralReferenceElement::SubtitleId(id) => children[within.clone()]
+            .iter()
+            .rposition(
+                |c| matches!(c, ActChild::Subtitle(Subtitle { identifier: Some(i), .. }) if i < id),
+            )
+            .map(|i| within.start + i..end_of_subtitle(children, within.clone(), within.start + i)),
+        _ => None,
+    };
+    Ok(previous.map_or(within.end, |r| r.end))
+}
+
+fn find_structural_element(
+    children: &[ActChild],
+    within: Range<usize>,
+    element_type: StructuralElementType,
+    id: NumericIdentifier,
+) -> Result<Range<usize>> {
+    let start = children[within.clone()]
+        .iter()
+        .position(|c| {
+            matches!(c, ActChild::StructuralElement(se)
+                if structural_level(se.element_type) == structural_level(element_type)
+                    && se.identifier == id)
+        })
+        .map(|i| within.start + i)
+        .ok_or_else(|| anyhow!("Could not find structural element {element_type:?} {id:?}"))?;
+    Ok(start..end_of_structural_element(children, within, start))
+}
+
+fn rfind_structural_element_before(
+    children: &[ActChild],
+    within: Range<usize>,
+    element_type: StructuralElementType,
+    id: NumericIdentifier,
+) -> Option<Range<usize>> {
+    let start = children[within.clone()]
+        .iter()
+        .rposition(|c| {
+            matches!(c, ActChild::StructuralElement(se)
+                if structural_level(se.element_type) == structural_level(element_type)
+                    && se.identifier < id)
+        })
+        .map(|i| within.start + i)?;
+    Some(start..end_of_structural_element(children, within, start))
+}
+
+fn end_of_structural_element(children: &[ActChild], within: Range<usize>, start: usize) -> usize {
+    let level = match &children[start] {
+        ActChild::StructuralElement(se) => structural_level(se.element_type),
+        _ => return start + 1,
+    };
+    children[start + 1..within.end]
+        .iter()
+        .position(|c| {
+            matches!(c, ActChild::StructuralElement(se) if structural_level(se.element_type) <= level)
+        })
+        .map_or(within.end, |i| start + 1 + i)
+}
+
+fn find_subtitle(
+    children: &[ActChild],
+    within: Range<usize>,
+    predicate: impl Fn(&Subtitle) -> bool,
+) -> Result<usize> {
+    children[within.clone()]
+        .iter()
+        .position(|c| matches!(c, ActChild::Subtitle(st) if predicate(st)))
+        .map(|i| within.start + i)
+        .ok_or_else(|| anyhow!("Could not find subtitle"))
+}
+
+fn end_of_subtitle(children: &[ActChild], within: Range<usize>, start: usize) -> usize {
+    children[start + 1..within.end]
+        .iter()
+        .position(|c| !matches!(c, ActChild::Article(_)))
+        .map_or(within.end, |i| start + 1 + i)
+}
+
+fn find_article(
+    children: &[ActChild],
+    within: Range<usize>,
+    article_id: ArticleIdentifier,
+) -> Result<usize> {
+    children[within.clone()]
+        .iter()
+        .position(|c| matches!(c, ActChild::Article(a) if a.identifier == article_id))
+        .map(|i| within.start + i)
+        .ok_or_else(|| anyhow!("Could not find article {article_id}"))
+}
+
+fn find_subtitle_before_article(
+    children: &[ActChild],
+    within: Range<usize>,
+    article_id: ArticleIdentifier,
+) -> Result<usize> {
+    let article_index = find_article(children, within.clone(), article_id)?;
+    ensure!(
+        article_index > within.start
+            && matches!(children[article_index - 1], ActChild::Subtitle(_)),
+        "There is no subtitle before article {article_id}"
+    );
+    Ok(article_index - 1)
+}
+
+fn first_article_id(content: &[ActChild]) -> Option<ArticleIdentifier> {
+    content.iter().find_map(|c| match c {
+        ActChild::Article(a) => Some(a.identifier),
+        _ => None,
+    })
+}
+
+fn last_article_id(content: &[ActChild]) -> Option<ArticleIdentifier> {
+    content.iter().rev().find_map(|c| match c {
+        ActChild::Article(a) => Some(a.identifier),
+        _ => None,
+    })
+}
+
+/// Higher level elements have lower numbers. Special and normal parts are on the same level.
+fn structural_level(element_type: StructuralElementType) -> u8 {
+    match element_type {
+        StructuralElementType::Book => 0,
+        StructuralElementType::Part { .. } => 1,
+        StructuralElementType::Title => 2,
+        StructuralElementType::Chapter => 3,
+    }
+}
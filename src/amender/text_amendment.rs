@@ -0,0 +1,208 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{anyhow, bail, ensure, Result};
+
+use super::{
+    structural_amendment::{find_existing, find_parent_range},
+    ModifyAct,
+};
+use crate::{
+    identifier::{ActIdentifier, IdentifierCommon},
+    reference::Reference,
+    semantic_info::{TextAmendment, TextAmendmentReference, TextAmendmentSAEPart},
+    structure::{Act, ActChild, ChildrenCommon, LastChange, SAEBody, SubArticleElement},
+    util::walker::SAEVisitorMut,
+};
+
+impl ModifyAct for TextAmendment {
+    fn apply(&self, act: &mut Act, change: &LastChange) -> Result<()> {
+        let replacement_count = match &self.reference {
+            TextAmendmentReference::SAE {
+                reference,
+                amended_part,
+            } => {
+                let mut visitor = TextReplacingVisitor {
+                    reference: reference.without_act(),
+                    amended_part,
+                    from: &self.from,
+                    to: &self.to,
+                    change,
+                    replacement_count: 0,
+                };
+                act.walk_saes_mut(&mut visitor)?;
+                visitor.replacement_count
+            }
+            TextAmendmentReference::Structural(position) => {
+                let within = find_parent_range(&act.children, position)?;
+                let range =
+                    find_existing(&act.children, within, &position.structural_element, &[])?;
+                let (title, last_change) = match &mut act.children[range.start] {
+                    ActChild::StructuralElement(se) => (&mut se.title, &mut se.last_change),
+                    ActChild::Subtitle(st) => (&mut st.title, &mut st.last_change),
+                    ActChild::Article(_) => {
+                        bail!("Structural text amendments can only modify titles")
+                    }
+                };
+                let count = replace_in(title, &self.from, &self.to);
+                if count > 0 {
+                    *last_change = Some(change.clone());
+                }
+                count
+            }
+            TextAmendmentReference::ArticleTitle(reference) => {
+                let article_range = reference
+                    .article()
+                    .ok_or_else(|| anyhow!("No article in article title amendment reference"))?;
+                let mut count = 0;
+                for article in act.articles_mut() {
+                    if let Some(title) = &mut article.title {
+                        if article_range.contains(article.identifier) {
+                            let article_count = replace_in(title, &self.from, &self.to);
+                            if article_count > 0 {
+                                article.last_change = Some(change.clone());
+                            }
+                            count += article_count;
+                        }
+                    }
+                }
+                count
+            }
+        };
+        ensure!(
+            replacement_count > 0,
+            "Could not find text '{}' to replace",
+            self.from
+        );
+        Ok(())
+    }
+
+    fn affected_act(&self) -> Option<ActIdentifier> {
+        match &self.reference {
+            TextAmendmentReference::SAE { reference, .. } => reference.act(),
+            TextAmendmentReference::Structural(position) => position.act,
+            TextAmendmentReference::ArticleTitle(reference) => reference.act(),
+        }
+    }
+}
+
+struct TextReplacingVisitor<'a> {
+    reference: Reference,
+    amended_part: &'a TextAmendmentSAEPart,
+    from: &'a str,
+    to: &'a str,
+    change: &'a LastChange,
+    replacement_count: usize,
+}
+
+impl<'a> SAEVisitorMut for TextReplacingVisitor<'a> {
+    fn on_enter<IT: IdentifierCommon, CT: ChildrenCommon>(
+        &mut self,
+        position: &Reference,
+        element: &mut SubArticleElement<IT, CT>,
+    ) -> Result<()> {
+        let position = position.without_act();
+        if !self.reference.contains(&position) {
+            return Ok(());
+        }
+        // Intros and wrap-ups are only amended in the referenced elements,
+        // not in their children.
+        let is_referenced_element = !self.reference.contains(&position.parent());
+        let count = match (&mut element.body, self.amended_part) {
+            (SAEBody::Text(text), TextAmendmentSAEPart::All) => {
+                replace_in(text, self.from, self.to)
+            }
+            (SAEBody::Children { intro, wrap_up, .. }, TextAmendmentSAEPart::All) => {
+                replace_in(intro, self.from, self.to)
+                    + wrap_up
+                        .as_mut()
+                        .map_or(0, |wrap_up| replace_in(wrap_up, self.from, self.to))
+            }
+            (SAEBody::Children { intro, .. }, TextAmendmentSAEPart::IntroOnly)
+                if is_referenced_element =>
+            {
+                replace_in(intro, self.from, self.to)
+            }
+            (
+                SAEBody::Children {
+                    wrap_up: Some(wrap_up),
+                    ..
+                },
+                TextAmendmentSAEPart::WrapUpOnly,
+            ) if is_referenced_element => replace_in(wrap_up, self.from, self.to),
+            _ => 0,
+        };
+        if count > 0 {
+            element.last_change = Some(self.change.clone());
+            self.replacement_count += count;
+        }
+        Ok(())
+    }
+}
+
+/// Replace all occurrences of `from` in `text`, and return the number of replacements
+fn replace_in(text: &mut String, from: &str, to: &str) -> usize {
+    let count = text.matches(from).count();
+    if count > 0 {
+        *text = text.replace(from, to);
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{structure::ChangeCause, util::singleton_yaml};
+
+    #[test]
+    fn test_missing_text_is_an_error() {
+        let mut act: Act = singleton_yaml::from_str(
+            r#"
+            identifier:
+              year: 2012
+              number: 100
+            subject: A tesztelésről
+            preamble: ""
+            publication_date: 2012-07-13
+            children:
+            - Article:
+                identifier: "1"
+                children:
+                - body: A miniszter rendeletben szabályozza.
+            "#,
+        )
+        .unwrap();
+        let amendment: TextAmendment = singleton_yaml::from_str(
+            r#"
+            reference:
+              SAE:
+                reference:
+                  act:
+                    year: 2012
+                    number: 100
+                  article: "1"
+            from: kormány
+            to: miniszter
+            "#,
+        )
+        .unwrap();
+        let change = LastChange {
+            date: chrono::NaiveDate::from_ymd_opt(2013, 1, 1).unwrap(),
+            cause: ChangeCause::Other("test".into()),
+        };
+        assert!(amendment.apply(&mut act, &change).is_err());
+    }
+}
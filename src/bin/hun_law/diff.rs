@@ -0,0 +1,72 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    fs::File,
+    io::{BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use hun_law::{diff::ActDiff, structure::Act, util::singleton_yaml};
+
+#[derive(clap::Args, Debug)]
+pub struct DiffArgs {
+    /// The old version of the act, in JSON or YAML format
+    old: PathBuf,
+    /// The new version of the act, in JSON or YAML format
+    new: PathBuf,
+    /// Output format
+    #[clap(value_enum, long, short = 't', default_value = "text")]
+    output_format: DiffFormat,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffFormat {
+    /// Word diff style text output
+    #[clap(alias("txt"))]
+    Text,
+    /// JSON output
+    Json,
+    /// HTML fragment with <ins> and <del> tags
+    Html,
+}
+
+pub fn run_diff(args: &DiffArgs) -> Result<()> {
+    let old = load_act(&args.old)?;
+    let new = load_act(&args.new)?;
+    let diff = ActDiff::from_acts(&old, &new)?;
+    let mut output = std::io::stdout();
+    match args.output_format {
+        DiffFormat::Text => diff.write_as_text(&mut output)?,
+        DiffFormat::Json => {
+            serde_json::to_writer(&mut output, &diff)?;
+            writeln!(output)?;
+        }
+        DiffFormat::Html => diff.write_as_html(&mut output)?,
+    }
+    Ok(())
+}
+
+fn load_act(path: &Path) -> Result<Act> {
+    let reader =
+        BufReader::new(File::open(path).with_context(|| anyhow!("Error opening {path:?}"))?);
+    if path.extension() == Some("json".as_ref()) {
+        serde_json::from_reader(reader).with_context(|| anyhow!("Error parsing {path:?}"))
+    } else {
+        singleton_yaml::from_reader(reader).with_context(|| anyhow!("Error parsing {path:?}"))
+    }
+}
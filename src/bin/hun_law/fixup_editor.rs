@@ -157,6 +157,7 @@ mod tests {
             ActIdentifier {
                 year: 2042,
                 number: 420,
+                ..Default::default()
             },
             temp_dir.path().to_owned(),
         )?;
@@ -0,0 +1,69 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use hun_law::{
+    act_locator::DEFAULT_INDEX_PATH,
+    mk_downloader::{cached_mk_issues, download_mk_issue, MkIssue, DEFAULT_MK_CROP},
+    mk_index::MkIndex,
+    parser::pdf::parse_pdf,
+};
+use log::info;
+
+#[derive(clap::Args, Debug)]
+pub struct IndexArgs {
+    /// Only index these MK issues (in YEAR/ISSUE format) instead of all cached ones
+    issues: Vec<String>,
+    /// The index file to update
+    #[clap(long, default_value = DEFAULT_INDEX_PATH)]
+    index: PathBuf,
+    /// Start from an empty index instead of updating the existing one
+    #[clap(long)]
+    rebuild: bool,
+    /// Cache directory used to store downloaded MK issue pdfs
+    #[clap(long, short, default_value = "./cache")]
+    cache_dir: PathBuf,
+}
+
+pub fn run_index(args: &IndexArgs) -> Result<()> {
+    let mut index = if args.rebuild || !args.index.exists() {
+        MkIndex::new()
+    } else {
+        MkIndex::load(&args.index)?
+    };
+    let issues = if args.issues.is_empty() {
+        cached_mk_issues(&args.cache_dir)?
+    } else {
+        args.issues
+            .iter()
+            .map(|s| s.parse())
+            .collect::<Result<Vec<MkIssue>>>()?
+    };
+    for issue in &issues {
+        let result = download_mk_issue(issue, &args.cache_dir)
+            .and_then(|body| parse_pdf(&body, DEFAULT_MK_CROP.clone()))
+            .and_then(|pages| index.add_issue(issue, &pages));
+        match result {
+            Ok(count) => info!("{issue}: {count} documents"),
+            Err(error) => log::error!("{issue}: {error:?}"),
+        }
+    }
+    index.save(&args.index)?;
+    info!("Wrote {} records into {:?}", index.len(), args.index);
+    Ok(())
+}
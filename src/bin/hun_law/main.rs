@@ -14,32 +14,45 @@
 // You should have received a copy of the GNU General Public License
 // along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
 
+mod diff;
 mod fixup_editor;
+mod index;
 
-use std::{collections::BTreeSet, fs::File, io::Write, path::PathBuf, str::FromStr};
+use std::{fs::File, io::Write, path::PathBuf, str::FromStr};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDate, NaiveDateTime};
 use clap::Parser;
+use diff::{run_diff, DiffArgs};
 use fixup_editor::run_fixup_editor;
 use hun_law::{
+    act_locator::ActLocator,
+    corpus::Corpus,
     fixups::Fixups,
     identifier::ActIdentifier,
     mk_downloader::{download_mk_issue, MkIssue, DEFAULT_MK_CROP},
     output::{CliOutput, OutputFormat},
     parser::pdf::parse_pdf,
     parser::{
-        mk_act_section::{parse_mk_pages_into_acts, ActRawText},
+        mk_act_section::{
+            compare_segmentation_strategies, parse_mk_pages_into_acts_using, ActRawText,
+            SegmentationStrategy,
+        },
+        mk_toc::{compare_with_toc, parse_mk_toc},
         structure::parse_act_structure,
     },
 };
+use index::{run_index, IndexArgs};
 use log::info;
-use serde::{Deserialize, Deserializer};
 
 /// Hun-Law output generator
 ///
 /// Downloads Magyar Közlöny issues as PDFs and converts the Acts in them to machine-parseable formats.
 #[derive(clap::Parser, Debug)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct HunLawArgs {
+    #[clap(subcommand)]
+    command: Option<HunLawCommand>,
     #[clap(required = true, name = "id")]
     /// Acts or Magyar Közlöny issues (if --mk is specified) to convert in long, YEAR/Number or YEAR/ISSUE format.
     /// Examples: "2013/31", "2012. évi C. törvény"
@@ -53,6 +66,15 @@ struct HunLawArgs {
     /// Do parsing only until and including this step
     #[clap(value_enum, long, short, default_value_t)]
     parse_until: ParsingStep,
+    /// Method of finding the documents in Magyar Közlöny issues
+    #[clap(value_enum, long, default_value_t)]
+    segmentation: SegmentationStrategy,
+    /// Compare the documents found by all segmentation strategies, and log the differences
+    #[clap(long)]
+    segmentation_report: bool,
+    /// Cross-check the documents found with the table of contents of the issue, and log the differences
+    #[clap(long)]
+    toc_report: bool,
     /// Interactively fix errors with a fixup editor, should they occur during parsing
     #[clap(long, short)]
     interactive: bool,
@@ -65,14 +87,30 @@ struct HunLawArgs {
     /// Output directory. If not specified, output is printed to stdout
     #[clap(long, short)]
     output_dir: Option<PathBuf>,
+    /// Corpus directory. If specified, fully parsed acts are also stored in the corpus
+    #[clap(long)]
+    corpus_dir: Option<PathBuf>,
     /// Cache directory used to store downloaded MK issue pdfs
     #[clap(long, short, default_value = "./cache")]
     cache_dir: PathBuf,
+    /// URL of other acts in links (applies to HTML and Markdown output only).
+    /// `{act}` is replaced with the compact identifier of the act, `{file}` with its file name stem.
+    /// By default, links point to the files written to the same output directory.
+    #[clap(long)]
+    act_url_pattern: Option<String>,
     /// Width of the word-wrapped text (applies to text output only)
     #[clap(long, short, default_value = "105")]
     width: usize,
 }
 
+#[derive(clap::Subcommand, Debug)]
+enum HunLawCommand {
+    /// Update the act to MK issue index from the downloaded MK issues
+    Index(IndexArgs),
+    /// Compare two versions of an act
+    Diff(DiffArgs),
+}
+
 #[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
 enum ParsingStep {
     /// Only parse the PDFs into a list of lines
@@ -99,6 +137,11 @@ fn main() -> Result<()> {
     .init();
 
     let mut args = HunLawArgs::parse();
+    match &args.command {
+        Some(HunLawCommand::Index(index_args)) => return run_index(index_args),
+        Some(HunLawCommand::Diff(diff_args)) => return run_diff(diff_args),
+        None => (),
+    }
     if args.output_dir.is_none() && args.output_format == OutputFormat::Plain {
         args.output_format = OutputFormat::Colored
     }
@@ -114,17 +157,35 @@ fn main() -> Result<()> {
             let pages = parse_pdf(&body, DEFAULT_MK_CROP.clone())?;
             if args.parse_until == ParsingStep::PdfLines {
                 let mut output = get_output(&mk_name, &args)?;
-                pages.cli_output(args.width, args.output_format, &mut output)?;
+                pages.cli_output(
+                    args.width,
+                    args.output_format,
+                    args.act_url_pattern.as_deref(),
+                    &mut output,
+                )?;
                 return Ok(());
             }
 
-            for act in parse_mk_pages_into_acts(&pages)? {
+            if args.segmentation_report {
+                for difference in compare_segmentation_strategies(&pages)? {
+                    log::warn!("{mk_name}: {difference:?}");
+                }
+            }
+
+            let acts_raw = parse_mk_pages_into_acts_using(&pages, args.segmentation)?;
+            if args.toc_report {
+                for difference in compare_with_toc(&parse_mk_toc(&pages)?, &acts_raw) {
+                    log::warn!("{mk_name}: {difference:?}");
+                }
+            }
+
+            for act in acts_raw {
                 if !acts.is_empty() && !acts.contains(&act.identifier) {
                     log::info!("Skipping {}", act.identifier);
                     continue;
                 }
 
-                let mut output = get_output(&act.identifier.to_string(), &args)?;
+                let mut output = get_output(&act.identifier.file_name_stem(), &args)?;
                 let process_result = if args.interactive {
                     process_single_act_interactive(act, &args, &mut output)
                 } else {
@@ -156,6 +217,9 @@ fn get_output(filename: &str, args: &HunLawArgs) -> Result<Box<dyn std::io::Writ
                 OutputFormat::Colored => "txt",
                 OutputFormat::Json => "json",
                 OutputFormat::Yaml => "yml",
+                OutputFormat::AkomaNtoso => "xml",
+                OutputFormat::Html => "html",
+                OutputFormat::Markdown => "md",
             };
             let path = odir.join(format!("{filename}.{extension}"));
             info!("Writing into {:?}", path);
@@ -168,24 +232,6 @@ fn get_output(filename: &str, args: &HunLawArgs) -> Result<Box<dyn std::io::Writ
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-struct ActToMkIssueRecord {
-    #[serde(deserialize_with = "deserialize_from_str")]
-    mk_issue: MkIssue,
-    #[serde(deserialize_with = "deserialize_from_str")]
-    act: ActIdentifier,
-}
-
-fn deserialize_from_str<'de, T, D>(deserializer: D) -> Result<T, D::Error>
-where
-    D: Deserializer<'de>,
-    T: FromStr,
-    <T as FromStr>::Err: std::fmt::Display,
-{
-    let buf = String::deserialize(deserializer)?;
-    T::from_str(&buf).map_err(serde::de::Error::custom)
-}
-
 fn get_issues(args: &HunLawArgs) -> Result<(Vec<MkIssue>, Vec<ActIdentifier>)> {
     if args.mk {
         Ok((
@@ -196,33 +242,13 @@ fn get_issues(args: &HunLawArgs) -> Result<(Vec<MkIssue>, Vec<ActIdentifier>)> {
             Vec::new(),
         ))
     } else {
-        let path = "./data/act_to_mk_issue.csv";
         let acts = args
             .ids
             .iter()
             .map(|s| ActIdentifier::from_str(s))
             .collect::<Result<Vec<_>>>()?;
-        let records = csv::Reader::from_path(path)
-            .with_context(|| anyhow!("Error opening {path}"))?
-            .deserialize()
-            .collect::<csv::Result<Vec<ActToMkIssueRecord>>>()
-            .with_context(|| anyhow!("Error parsing {path}"))?;
-        let mut issues = BTreeSet::new();
-        for act in &acts {
-            let issue = records
-                .iter()
-                .find_map(|r| {
-                    if r.act == *act {
-                        Some(r.mk_issue.clone())
-                    } else {
-                        None
-                    }
-                })
-                .ok_or_else(|| anyhow!("Could not find {act} in {path}"))?;
-            issues.insert(issue);
-        }
-
-        Ok((issues.into_iter().collect(), acts))
+        let issues = ActLocator::from_default_index()?.issues_of(&acts)?;
+        Ok((issues, acts))
     }
 }
 
@@ -257,18 +283,36 @@ fn process_single_act(
     act_raw.remove_double_empty_lines();
 
     if args.parse_until == ParsingStep::ActLines {
-        return act_raw.cli_output(args.width, args.output_format, output);
+        return act_raw.cli_output(
+            args.width,
+            args.output_format,
+            args.act_url_pattern.as_deref(),
+            output,
+        );
     }
 
     let mut act = parse_act_structure(&act_raw)?;
 
     if args.parse_until == ParsingStep::Structure {
-        return act.cli_output(args.width, args.output_format, output);
+        return act.cli_output(
+            args.width,
+            args.output_format,
+            args.act_url_pattern.as_deref(),
+            output,
+        );
     }
 
     act.add_semantic_info()?;
     act.convert_block_amendments()?;
-    act.cli_output(args.width, args.output_format, output)?;
+    if let Some(corpus_dir) = &args.corpus_dir {
+        Corpus::new(corpus_dir).store(&act, today()?)?;
+    }
+    act.cli_output(
+        args.width,
+        args.output_format,
+        args.act_url_pattern.as_deref(),
+        output,
+    )?;
     if args.force_fixup_editor {
         Err(anyhow!("Forcing fixup editor because of parameters"))
     } else {
@@ -276,6 +320,15 @@ fn process_single_act(
     }
 }
 
+fn today() -> Result<NaiveDate> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    Ok(NaiveDateTime::from_timestamp_opt(timestamp.try_into()?, 0)
+        .ok_or_else(|| anyhow!("Invalid system time"))?
+        .date())
+}
+
 fn confirm(s: &str) -> Result<bool> {
     eprint!("{s} [Y/n]");
     let mut buf = String::new();
@@ -0,0 +1,334 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    cmp::Ordering,
+    fs::{create_dir_all, read_dir, File},
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{identifier::ActIdentifier, structure::Act, util::compact_string::CompactString};
+
+/// Version of the parsing pipeline, stored with every act in the corpus
+pub const PIPELINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Identifies a single stored version of an act
+///
+/// Versions are ordered by parse date, and then by the numeric components of the
+/// pipeline version, so that "0.10.0" comes after "0.9.0".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusVersion {
+    pub parse_date: NaiveDate,
+    pub pipeline_version: String,
+}
+
+impl PartialOrd for CorpusVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CorpusVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.parse_date
+            .cmp(&other.parse_date)
+            .then_with(|| {
+                version_components(&self.pipeline_version)
+                    .cmp(version_components(&other.pipeline_version))
+            })
+            .then_with(|| self.pipeline_version.cmp(&other.pipeline_version))
+    }
+}
+
+/// The dot separated components of a version string, split into a leading number
+/// and the rest, e.g. "1.2.3-rc1" becomes (1, ""), (2, ""), (3, "-rc1")
+fn version_components(version: &str) -> impl Iterator<Item = (u64, &str)> {
+    version.split('.').map(|component| {
+        let digits_end = component
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(component.len());
+        (
+            component[..digits_end].parse().unwrap_or(0),
+            &component[digits_end..],
+        )
+    })
+}
+
+/// A collection of parsed acts stored on disk.
+///
+/// The layout is `<base_dir>/<year>/<number>/<parse date>_<pipeline version>.json`.
+/// For decrees and resolutions, the number is followed by the rest of the compact
+/// identifier string, with the trailing dot replaced by an underscore (Windows does
+/// not allow file names ending in a dot), e.g. `2013/123.R.4.11.Korm_`
+/// Storing the same act on the same day with the same pipeline version
+/// overwrites the previous version.
+#[derive(Debug, Clone)]
+pub struct Corpus {
+    base_dir: PathBuf,
+}
+
+impl Corpus {
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+        }
+    }
+
+    pub fn store(&self, act: &Act, parse_date: NaiveDate) -> Result<CorpusVersion> {
+        let version = CorpusVersion {
+            parse_date,
+            pipeline_version: PIPELINE_VERSION.to_owned(),
+        };
+        let act_dir = self.act_dir(act.identifier);
+        create_dir_all(&act_dir)?;
+        let path = act_dir.join(version.file_name());
+        serde_json::to_writer(BufWriter::new(File::create(&path)?), act)
+            .with_context(|| anyhow!("Error writing {path:?}"))?;
+        Ok(version)
+    }
+
+    /// All stored versions of an act, oldest first
+    pub fn versions(&self, act_id: ActIdentifier) -> Result<Vec<CorpusVersion>> {
+        let act_dir = self.act_dir(act_id);
+        if !act_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut result = Vec::new();
+        for entry in read_dir(&act_dir)? {
+            let file_name = entry?.file_name();
+            if let Some(version) = file_name.to_str().and_then(CorpusVersion::from_file_name) {
+                result.push(version);
+            }
+        }
+        result.sort();
+        Ok(result)
+    }
+
+    pub fn get_version(&self, act_id: ActIdentifier, version: &CorpusVersion) -> Result<Act> {
+        let path = self.act_dir(act_id).join(version.file_name());
+        serde_json::from_reader(BufReader::new(
+            File::open(&path).with_context(|| anyhow!("Error opening {path:?}"))?,
+        ))
+        .with_context(|| anyhow!("Error parsing {path:?}"))
+    }
+
+    /// The latest version of an act, if it is in the corpus
+    pub fn get(&self, act_id: ActIdentifier) -> Result<Option<Act>> {
+        match self.versions(act_id)?.last() {
+            Some(version) => Ok(Some(self.get_version(act_id, version)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn contains(&self, act_id: ActIdentifier) -> Result<bool> {
+        Ok(!self.versions(act_id)?.is_empty())
+    }
+
+    /// The identifiers of all acts in the corpus, in order
+    pub fn act_ids(&self) -> Result<Vec<ActIdentifier>> {
+        let mut result = Vec::new();
+        for year in numeric_subdirs(&self.base_dir)? {
+            for name in subdirs(&self.base_dir.join(year.to_string()))? {
+                let compact = format!("{year}.{}", dir_name_to_compact(&name));
+                if let Ok(act_id) = ActIdentifier::from_compact_string(compact) {
+                    if self.contains(act_id)? {
+                        result.push(act_id);
+                    }
+                }
+            }
+        }
+        result.sort();
+        Ok(result)
+    }
+
+    /// Iterate over the latest version of all acts in the corpus, in order
+    pub fn iter(&self) -> Result<impl Iterator<Item = Result<Act>> + '_> {
+        Ok(self.act_ids()?.into_iter().map(|act_id| {
+            self.get(act_id)?
+                .ok_or_else(|| anyhow!("{act_id} disappeared from the corpus"))
+        }))
+    }
+
+    fn act_dir(&self, act_id: ActIdentifier) -> PathBuf {
+        let year = act_id.year.to_string();
+        let compact = act_id.compact_string().to_string();
+        // The compact string always starts with "<year>."
+        self.base_dir
+            .join(&year)
+            .join(compact_to_dir_name(&compact[year.len() + 1..]))
+    }
+}
+
+/// Issuers cannot contain underscores, so this is reversible.
+fn compact_to_dir_name(compact: &str) -> String {
+    match compact.strip_suffix('.') {
+        Some(stripped) => format!("{stripped}_"),
+        None => compact.to_owned(),
+    }
+}
+
+fn dir_name_to_compact(dir_name: &str) -> String {
+    match dir_name.strip_suffix('_') {
+        Some(stripped) => format!("{stripped}."),
+        None => dir_name.to_owned(),
+    }
+}
+
+impl CorpusVersion {
+    fn file_name(&self) -> String {
+        format!("{}_{}.json", self.parse_date, self.pipeline_version)
+    }
+
+    fn from_file_name(file_name: &str) -> Option<Self> {
+        let (parse_date, pipeline_version) = file_name.strip_suffix(".json")?.split_once('_')?;
+        Some(Self {
+            parse_date: parse_date.parse().ok()?,
+            pipeline_version: pipeline_version.to_owned(),
+        })
+    }
+}
+
+/// Subdirectories which have a number as their name. Other entries are ignored.
+fn numeric_subdirs(dir: &Path) -> Result<Vec<i32>> {
+    Ok(subdirs(dir)?
+        .into_iter()
+        .filter_map(|name| name.parse().ok())
+        .collect())
+}
+
+/// Names of the subdirectories with valid UTF-8 names. Other entries are ignored.
+fn subdirs(dir: &Path) -> Result<Vec<String>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut result = Vec::new();
+    for entry in read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            if let Ok(name) = entry.file_name().into_string() {
+                result.push(name);
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::singleton_yaml;
+
+    fn test_act(year: i16, number: i32, subject: &str) -> Act {
+        let mut act: Act = singleton_yaml::from_str(
+            r#"
+            identifier:
+              year: 2012
+              number: 1
+            subject: ""
+            preamble: ""
+            publication_date: 2012-07-13
+            children:
+            - Article:
+                identifier: "1"
+                children:
+                - body: Szöveg.
+            "#,
+        )
+        .unwrap();
+        act.identifier = ActIdentifier {
+            year,
+            number,
+            ..Default::default()
+        };
+        act.subject = subject.to_owned();
+        act
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let corpus = Corpus::new(dir.path());
+        let act_id = ActIdentifier {
+            year: 2012,
+            number: 100,
+            ..Default::default()
+        };
+        assert_eq!(corpus.get(act_id).unwrap(), None);
+
+        let date1 = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let date2 = NaiveDate::from_ymd_opt(2022, 2, 1).unwrap();
+        corpus.store(&test_act(2012, 100, "Régi"), date1).unwrap();
+        let new_version = corpus.store(&test_act(2012, 100, "Új"), date2).unwrap();
+        corpus.store(&test_act(2011, 5, "Másik"), date1).unwrap();
+        let mut decree = test_act(2012, 100, "Rendelet");
+        decree.identifier = "100/2012. (VII. 13.) Korm. rendelet".parse().unwrap();
+        corpus.store(&decree, date1).unwrap();
+
+        assert_eq!(corpus.versions(act_id).unwrap().len(), 2);
+        assert_eq!(corpus.versions(act_id).unwrap()[1], new_version);
+        assert_eq!(corpus.get(act_id).unwrap().unwrap().subject, "Új");
+        assert_eq!(
+            corpus.act_ids().unwrap(),
+            vec![
+                ActIdentifier {
+                    year: 2011,
+                    number: 5,
+                    ..Default::default()
+                },
+                act_id,
+                decree.identifier,
+            ]
+        );
+        let subjects: Vec<String> = corpus
+            .iter()
+            .unwrap()
+            .map(|act| act.unwrap().subject)
+            .collect();
+        assert_eq!(subjects, vec!["Másik", "Új", "Rendelet"]);
+        assert!(dir.path().join("2012").join("100.R.7.13.Korm_").is_dir());
+    }
+
+    #[test]
+    fn test_version_order() {
+        let version = |date: NaiveDate, pipeline_version: &str| CorpusVersion {
+            parse_date: date,
+            pipeline_version: pipeline_version.to_owned(),
+        };
+        let date1 = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let date2 = NaiveDate::from_ymd_opt(2022, 2, 1).unwrap();
+        let mut versions = vec![
+            version(date2, "0.1.0"),
+            version(date1, "0.10.0"),
+            version(date1, "0.9.0"),
+            version(date1, "0.9.1"),
+            version(date1, "1.0.0"),
+        ];
+        versions.sort();
+        let expected = vec![
+            version(date1, "0.9.0"),
+            version(date1, "0.9.1"),
+            version(date1, "0.10.0"),
+            version(date1, "1.0.0"),
+            version(date2, "0.1.0"),
+        ];
+        assert_eq!(versions, expected);
+    }
+}
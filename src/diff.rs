@@ -0,0 +1,481 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeMap, io::Write};
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+
+use crate::{
+    element_id::{ElementId, ElementRef, StructuralIdPart},
+    identifier::IdentifierCommon,
+    output::{
+        akoma_ntoso::escape,
+        text::{TextOutput, TextOutputParams},
+    },
+    structure::{
+        Act, ChildrenCommon, Paragraph, ParagraphChildren, SAEBody, SAEHeaderString,
+        SubArticleElement,
+    },
+};
+
+/// The differences between two versions of the same act, in document order.
+///
+/// Elements are aligned by their `ElementId`, except for unnumbered subtitles: their
+/// ids are positional, so they are aligned by their parent and title instead.
+/// Only the own text of the elements
+/// is compared: the text of the children is part of the children's diffs, but
+/// quoted blocks and block amendments are part of their parent's text.
+/// Structured attachments are compared point by point, others by their lines.
+/// Footnotes and semantic info are not compared.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActDiff {
+    pub elements: Vec<ElementDiff>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ElementDiff {
+    Added {
+        id: ElementId,
+        text: String,
+    },
+    Removed {
+        id: ElementId,
+        text: String,
+    },
+    Modified {
+        id: ElementId,
+        chunks: Vec<TextChunk>,
+    },
+}
+
+/// Part of a word level text diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextChunk {
+    Equal(String),
+    Removed(String),
+    Added(String),
+}
+
+impl ActDiff {
+    pub fn from_acts(old: &Act, new: &Act) -> Result<Self> {
+        ensure!(
+            old.identifier == new.identifier,
+            "Cannot diff different acts: {} and {}",
+            old.identifier,
+            new.identifier
+        );
+        let old_elements = element_texts(old)?;
+        let new_elements = element_texts(new)?;
+        let old_keys = alignment_keys(&old_elements);
+        let new_keys = alignment_keys(&new_elements);
+        let old_indices: BTreeMap<&AlignmentKey, usize> = old_keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (key, i))
+            .collect();
+        let new_indices: BTreeMap<&AlignmentKey, usize> = new_keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (key, i))
+            .collect();
+
+        let mut elements = Vec::new();
+        // Old elements before this index are already processed
+        let mut old_pos = 0;
+        for ((id, new_text), key) in new_elements.iter().zip(&new_keys) {
+            match old_indices.get(key) {
+                Some(&old_index) => {
+                    // Removed elements are reported where they were in the old version
+                    if old_index >= old_pos {
+                        for i in old_pos..old_index {
+                            let (old_id, old_text) = &old_elements[i];
+                            if !new_indices.contains_key(&old_keys[i]) {
+                                elements.push(ElementDiff::Removed {
+                                    id: old_id.clone(),
+                                    text: old_text.clone(),
+                                });
+                            }
+                        }
+                        old_pos = old_index + 1;
+                    }
+                    let old_text = &old_elements[old_index].1;
+                    if old_text != new_text {
+                        elements.push(ElementDiff::Modified {
+                            id: id.clone(),
+                            chunks: word_diff(old_text, new_text),
+                        });
+                    }
+                }
+                None => elements.push(ElementDiff::Added {
+                    id: id.clone(),
+                    text: new_text.clone(),
+                }),
+            }
+        }
+        for i in old_pos..old_elements.len() {
+            let (old_id, old_text) = &old_elements[i];
+            if !new_indices.contains_key(&old_keys[i]) {
+                elements.push(ElementDiff::Removed {
+                    id: old_id.clone(),
+                    text: old_text.clone(),
+                });
+            }
+        }
+        Ok(Self { elements })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Write the diff in a `git diff --word-diff` like format
+    pub fn write_as_text(&self, writer: &mut impl Write) -> Result<()> {
+        for element in &self.elements {
+            match element {
+                ElementDiff::Added { id, text } => writeln!(writer, "+ {id}: {text}")?,
+                ElementDiff::Removed { id, text } => writeln!(writer, "- {id}: {text}")?,
+                ElementDiff::Modified { id, chunks } => {
+                    write!(writer, "~ {id}: ")?;
+                    for chunk in chunks {
+                        match chunk {
+                            TextChunk::Equal(text) => write!(writer, "{text}")?,
+                            TextChunk::Removed(text) => write!(writer, "[-{text}-]")?,
+                            TextChunk::Added(text) => write!(writer, "{{+{text}+}}")?,
+                        }
+                    }
+                    writeln!(writer)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the diff as an HTML fragment, with `<ins>` and `<del>` tags
+    pub fn write_as_html(&self, writer: &mut impl Write) -> Result<()> {
+        writeln!(writer, r#"<div class="act-diff">"#)?;
+        for element in &self.elements {
+            let (class, id, content) = match element {
+                ElementDiff::Added { id, text } => {
+                    ("added", id, format!("<ins>{}</ins>", escape(text)))
+                }
+                ElementDiff::Removed { id, text } => {
+                    ("removed", id, format!("<del>{}</del>", escape(text)))
+                }
+                ElementDiff::Modified { id, chunks } => {
+                    let content = chunks
+                        .iter()
+                        .map(|chunk| match chunk {
+                            TextChunk::Equal(text) => escape(text),
+                            TextChunk::Removed(text) => format!("<del>{}</del>", escape(text)),
+                            TextChunk::Added(text) => format!("<ins>{}</ins>", escape(text)),
+                        })
+                        .collect();
+                    ("modified", id, content)
+                }
+            };
+            writeln!(
+                writer,
+                r#"<div class="{class}"><span class="element-id">{}</span> {content}</div>"#,
+                escape(&id.to_string())
+            )?;
+        }
+        writeln!(writer, "</div>")?;
+        Ok(())
+    }
+}
+
+fn word_diff(old: &str, new: &str) -> Vec<TextChunk> {
+    let mut result: Vec<TextChunk> = Vec::new();
+    for change in TextDiff::from_words(old, new).iter_all_changes() {
+        let value = change.value();
+        match (result.last_mut(), change.tag()) {
+            (Some(TextChunk::Equal(text)), ChangeTag::Equal)
+            | (Some(TextChunk::Removed(text)), ChangeTag::Delete)
+            | (Some(TextChunk::Added(text)), ChangeTag::Insert) => text.push_str(value),
+            (_, ChangeTag::Equal) => result.push(TextChunk::Equal(value.to_owned())),
+            (_, ChangeTag::Delete) => result.push(TextChunk::Removed(value.to_owned())),
+            (_, ChangeTag::Insert) => result.push(TextChunk::Added(value.to_owned())),
+        }
+    }
+    result
+}
+
+/// What elements of the two versions are matched by
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum AlignmentKey<'a> {
+    Id(&'a ElementId),
+    UnnumberedSubtitle {
+        parent: &'a [StructuralIdPart],
+        title: &'a str,
+        /// Subtitles with the same title in the same parent are matched in order
+        occurrence: usize,
+    },
+}
+
+fn alignment_keys(elements: &[(ElementId, String)]) -> Vec<AlignmentKey<'_>> {
+    let mut result: Vec<AlignmentKey> = Vec::new();
+    for (id, text) in elements {
+        let key = match id.structural_path().split_last() {
+            Some((StructuralIdPart::UnnumberedSubtitle(_), parent)) => {
+                let occurrence = result
+                    .iter()
+                    .filter(|key| {
+                        matches!(key, AlignmentKey::UnnumberedSubtitle { parent: p, title: t, .. }
+                            if *p == parent && *t == text)
+                    })
+                    .count();
+                AlignmentKey::UnnumberedSubtitle {
+                    parent,
+                    title: text,
+                    occurrence,
+                }
+            }
+            _ => AlignmentKey::Id(id),
+        };
+        result.push(key);
+    }
+    result
+}
+
+fn element_texts(act: &Act) -> Result<Vec<(ElementId, String)>> {
+    act.elements()?
+        .into_iter()
+        .map(|(id, element)| Ok((id, element_text(element)?)))
+        .collect()
+}
+
+/// The text of the element without its children
+fn element_text(element: ElementRef) -> Result<String> {
+    Ok(match element {
+        ElementRef::StructuralElement(se) => {
+            format!("{} {}", se.header_string()?, se.title)
+        }
+        ElementRef::Subtitle(st) => match st.identifier {
+            Some(identifier) => format!("{}. {}", identifier.with_slash(), st.title),
+            None => st.title.clone(),
+        },
+        ElementRef::Article(article) => {
+            let mut text = article.header_string();
+            if let Some(title) = &article.title {
+                text.push_str(&format!(" [{title}]"));
+            }
+            // The default paragraph has the same id as the article, so it is part of it
+            if let [paragraph] = article.children.as_slice() {
+                if paragraph.identifier.is_empty() {
+                    text.push(' ');
+                    text.push_str(&paragraph_text(paragraph)?);
+                }
+            }
+            text
+        }
+        ElementRef::Paragraph(x) => paragraph_text(x)?,
+        ElementRef::AlphabeticPoint(x) => sae_text(x, None),
+        ElementRef::NumericPoint(x) => sae_text(x, None),
+        ElementRef::AlphabeticSubpoint(x) => sae_text(x, None),
+        ElementRef::NumericSubpoint(x) => sae_text(x, None),
+        ElementRef::Attachment(attachment) => {
+            let mut parts = vec![attachment.header_string(), attachment.title.clone()];
+            // Structured contents are compared as separate elements
+            if attachment.children.is_none() {
+                parts.extend(attachment.lines.iter().cloned());
+            }
+            parts.retain(|p| !p.is_empty());
+            parts.join(" ")
+        }
+    })
+}
+
+/// Quoted blocks and block amendments are part of the text of the paragraph
+fn paragraph_text(paragraph: &Paragraph) -> Result<String> {
+    let inline_children = match &paragraph.body {
+        SAEBody::Children {
+            children:
+                children @ (ParagraphChildren::QuotedBlock(_)
+                | ParagraphChildren::BlockAmendment(_)
+                | ParagraphChildren::StructuralBlockAmendment(_)),
+            ..
+        } => {
+            let mut text = Vec::new();
+            children.write_as_text(&mut text, TextOutputParams::new(1000, false))?;
+            // The line structure of the text output is not relevant here
+            let text = String::from_utf8(text)?;
+            Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+        }
+        _ => None,
+    };
+    Ok(sae_text(paragraph, inline_children))
+}
+
+fn sae_text<IT, CT>(sae: &SubArticleElement<IT, CT>, inline_children: Option<String>) -> String
+where
+    SubArticleElement<IT, CT>: SAEHeaderString,
+    IT: IdentifierCommon,
+    CT: ChildrenCommon,
+{
+    let mut parts = vec![sae.header_string()];
+    match &sae.body {
+        SAEBody::Text(text) => parts.push(text.clone()),
+        SAEBody::Children { intro, wrap_up, .. } => {
+            parts.push(intro.clone());
+            parts.extend(inline_children);
+            parts.extend(wrap_up.clone());
+        }
+    }
+    parts.retain(|p| !p.is_empty());
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::util::singleton_yaml;
+
+    const OLD_ACT: &str = r#"
+        identifier:
+          year: 2012
+          number: 100
+        subject: A tesztelésről
+        preamble: ""
+        publication_date: 2012-07-13
+        children:
+        - Subtitle:
+            identifier: "1"
+            title: Fogalmak
+        - Article:
+            identifier: "1"
+            children:
+            - identifier: "1"
+              body: "Ez a régi szöveg."
+            - identifier: "2"
+              body: "Ez a bekezdés megszűnik."
+        - Subtitle:
+            title: Módosítások
+        - Article:
+            identifier: "2"
+            children:
+            - body:
+                intro: "A Ptk. 1:3. §-a helyébe a következő rendelkezés lép:"
+                children:
+                  BlockAmendment:
+                    children:
+                      Paragraph:
+                      - identifier: "2"
+                        body: "Régi módosítás."
+        attachments:
+        - identifier: "1"
+          title: Díjtáblázat
+          lines:
+          - "Alapdíj: 100 Ft"
+        - identifier: "2"
+          lines: []
+          children:
+            NumericPoint:
+            - identifier: "1"
+              body: "Előkészítés"
+            - identifier: "2"
+              body: "Régi végrehajtás"
+    "#;
+
+    const NEW_ACT: &str = r#"
+        identifier:
+          year: 2012
+          number: 100
+        subject: A tesztelésről
+        preamble: ""
+        publication_date: 2012-07-13
+        children:
+        - Subtitle:
+            identifier: "1"
+            title: Fogalmak
+        - Article:
+            identifier: "1"
+            children:
+            - identifier: "1"
+              body: "Ez az új szöveg."
+            - identifier: "1a"
+              body: "Beszúrt bekezdés."
+        - Subtitle:
+            title: Beszúrt alcím
+        - Subtitle:
+            title: Módosítások
+        - Article:
+            identifier: "2"
+            children:
+            - body:
+                intro: "A Ptk. 1:3. §-a helyébe a következő rendelkezés lép:"
+                children:
+                  BlockAmendment:
+                    children:
+                      Paragraph:
+                      - identifier: "2"
+                        body: "Új módosítás."
+        - Subtitle:
+            identifier: "2"
+            title: Záró rendelkezések
+        attachments:
+        - identifier: "1"
+          title: Díjtáblázat
+          lines:
+          - "Alapdíj: 200 Ft"
+        - identifier: "2"
+          lines: []
+          children:
+            NumericPoint:
+            - identifier: "1"
+              body: "Előkészítés"
+            - identifier: "2"
+              body: "Új végrehajtás"
+    "#;
+
+    #[test]
+    fn test_act_diff() {
+        let old: Act = singleton_yaml::from_str(OLD_ACT).unwrap();
+        let new: Act = singleton_yaml::from_str(NEW_ACT).unwrap();
+        assert!(ActDiff::from_acts(&old, &old).unwrap().is_empty());
+
+        let diff = ActDiff::from_acts(&old, &new).unwrap();
+        let mut text = Vec::new();
+        diff.write_as_text(&mut text).unwrap();
+        assert_eq!(
+            String::from_utf8(text).unwrap(),
+            concat!(
+                "~ 2012.100/art_1/para_1: (1) Ez [-a-]{+az+} [-régi-]{+új+} szöveg.\n",
+                "+ 2012.100/art_1/para_1a: (1a) Beszúrt bekezdés.\n",
+                // The ids of the following unnumbered subtitles change, but they are not reported
+                "+ 2012.100/subtitle_nn1: Beszúrt alcím\n",
+                "- 2012.100/art_1/para_2: (2) Ez a bekezdés megszűnik.\n",
+                "~ 2012.100/art_2: 2. § A Ptk. 1:3. §-a helyébe a következő rendelkezés lép: „ (2) [-Régi-]{+Új+} módosítás. ”\n",
+                "+ 2012.100/subtitle_2: 2. Záró rendelkezések\n",
+                "~ 2012.100/att_1: 1. melléklet Díjtáblázat Alapdíj: [-100-]{+200+} Ft\n",
+                "~ 2012.100/att_2/point_2: 2. [-Régi-]{+Új+} végrehajtás\n",
+            )
+        );
+
+        let mut html = Vec::new();
+        diff.write_as_html(&mut html).unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert!(html.contains(
+            r#"<div class="modified"><span class="element-id">2012.100/art_1/para_1</span> (1) Ez <del>a</del><ins>az</ins> <del>régi</del><ins>új</ins> szöveg.</div>"#
+        ), "{html}");
+
+        let json = serde_json::to_string(&diff).unwrap();
+        assert_eq!(serde_json::from_str::<ActDiff>(&json).unwrap(), diff);
+    }
+}
@@ -0,0 +1,796 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, bail, ensure, Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    identifier::{ActIdentifier, IdentifierCommon, NumericIdentifier},
+    reference::{to_element::ReferenceToElement, Reference},
+    structure::{
+        Act, ActChild, AlphabeticPoint, AlphabeticPointChildren, AlphabeticSubpoint,
+        AlphabeticSubpointChildren, Article, Attachment, ChildrenCommon, NumericPoint,
+        NumericPointChildren, NumericSubpoint, NumericSubpointChildren, Paragraph,
+        ParagraphChildren, SAEBody, StructuralElement, StructuralElementType, SubArticleElement,
+        Subtitle,
+    },
+    util::compact_string::CompactString,
+};
+
+/// Canonical, round-trippable identifier of an element of an act.
+///
+/// Articles and SAEs are identified by their position, e.g.
+/// `2012.100/art_3:15/para_2/point_a/subpoint_ab`. The default paragraph of an
+/// article has the same id as the article itself.
+///
+/// Structural elements and subtitles are identified by their path in the structure,
+/// e.g. `2013.5/book_1/part_2/chp_3/subtitle_4`. Subtitles without an identifier
+/// are numbered in order of appearance within their parent, e.g. `2013.5/chp_3/subtitle_nn1`.
+/// These ids are positional: inserting an unnumbered subtitle changes the ids of
+/// the following ones in the same parent.
+///
+/// Attachments are identified by their number, e.g. `2012.100/att_2`, and the points
+/// in them by their position within the attachment, e.g. `2012.100/att_2/point_3`.
+///
+/// The act itself is identified by its compact identifier, e.g. `2012.100`.
+///
+/// The HTML, Markdown and Akoma Ntoso outputs use the same ids as anchors,
+/// see [`ElementId::anchor`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String")]
+#[serde(try_from = "String")]
+pub struct ElementId {
+    act: ActIdentifier,
+    /// Path of a structural element or subtitle. Empty for articles and SAEs.
+    structural: Vec<StructuralIdPart>,
+    /// The attachment containing the element, or the attachment itself
+    attachment: Option<NumericIdentifier>,
+    /// Position of an article or SAE. Act-only for structural elements. Relative to the
+    /// attachment (i.e. without the act) for elements in attachments, and empty for
+    /// the attachments themselves.
+    position: Reference,
+}
+
+/// One level of the path of a structural element or subtitle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StructuralIdPart {
+    Book(NumericIdentifier),
+    Part(NumericIdentifier),
+    Title(NumericIdentifier),
+    Chapter(NumericIdentifier),
+    Subtitle(NumericIdentifier),
+    UnnumberedSubtitle(u16),
+}
+
+/// The path of the structural elements and subtitles, while going through the
+/// children of an act in order.
+#[derive(Debug, Clone, Default)]
+pub struct StructuralPath {
+    path: Vec<StructuralIdPart>,
+    /// Number of subtitles without an identifier since the last structural element
+    unnumbered_subtitles: u16,
+}
+
+/// A borrowed element of an act, as returned by the id lookup functions
+#[derive(Debug, Clone, Copy)]
+pub enum ElementRef<'a> {
+    StructuralElement(&'a StructuralElement),
+    Subtitle(&'a Subtitle),
+    Article(&'a Article),
+    Paragraph(&'a Paragraph),
+    AlphabeticPoint(&'a AlphabeticPoint),
+    NumericPoint(&'a NumericPoint),
+    AlphabeticSubpoint(&'a AlphabeticSubpoint),
+    NumericSubpoint(&'a NumericSubpoint),
+    Attachment(&'a Attachment),
+}
+
+impl ElementId {
+    /// Id of an article or SAE. The reference must contain the act, and must not be a range.
+    pub fn from_reference(reference: &Reference) -> Result<Self> {
+        let act = reference
+            .act()
+            .ok_or_else(|| anyhow!("Reference {reference:?} does not contain the act"))?;
+        ensure!(
+            *reference == reference.first_in_range(),
+            "Reference {reference:?} is a range"
+        );
+        Ok(Self {
+            act,
+            structural: Vec::new(),
+            attachment: None,
+            position: reference.clone(),
+        })
+    }
+
+    pub fn act(&self) -> ActIdentifier {
+        self.act
+    }
+
+    /// The absolute reference of the element. None for structural elements, subtitles
+    /// and attachments (and their contents).
+    pub fn reference(&self) -> Option<Reference> {
+        if self.is_structural() || self.attachment.is_some() {
+            None
+        } else {
+            Some(self.position.clone())
+        }
+    }
+
+    /// Anchor of the element in the HTML, Markdown and Akoma Ntoso outputs of the act.
+    /// See [`position_anchor`], [`structural_anchor`] and [`attachment_anchor`].
+    pub fn anchor(&self) -> String {
+        anchor_from_parts(self.parts())
+    }
+
+    /// The parts of the id after the act
+    fn parts(&self) -> Vec<String> {
+        let mut result: Vec<String> = self.structural.iter().map(|p| p.to_string()).collect();
+        result.extend(self.attachment.map(|a| format!("att_{a}")));
+        result.extend(position_parts(&self.position));
+        result
+    }
+
+    /// The attachment containing the element, or the attachment itself
+    pub fn attachment(&self) -> Option<NumericIdentifier> {
+        self.attachment
+    }
+
+    pub fn is_structural(&self) -> bool {
+        !self.structural.is_empty()
+    }
+
+    /// The path of a structural element or subtitle
+    pub fn structural_path(&self) -> &[StructuralIdPart] {
+        &self.structural
+    }
+}
+
+impl Display for ElementId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.act.fmt_compact_string(f)?;
+        for part in self.parts() {
+            write!(f, "/{part}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Id parts of the article and SAE levels of a position, e.g. `["art_3:15", "para_2"]`
+fn position_parts(position: &Reference) -> Vec<String> {
+    let mut result = Vec::new();
+    if let Some(article) = position.article() {
+        // Books are separated by a colon, like in the text of the acts
+        let article = article.compact_string().to_string().replace('.', ":");
+        result.push(format!("art_{article}"));
+    }
+    if let Some(paragraph) = position.paragraph() {
+        result.push(format!("para_{}", paragraph.compact_string()));
+    }
+    if let Some(point) = position.point() {
+        result.push(format!("point_{}", point.compact_string()));
+    }
+    if let Some(subpoint) = position.subpoint() {
+        result.push(format!("subpoint_{}", subpoint.compact_string()));
+    }
+    result
+}
+
+/// Anchor of an article or SAE in the HTML, Markdown and Akoma Ntoso outputs.
+///
+/// It is the element id without the act, with `__` separators, and `-` between
+/// the book and the article, e.g. `art_3-15__para_2__point_a`. The position may be
+/// relative (e.g. in quoted structures), and ranges are anchored at their first element.
+pub fn position_anchor(position: &Reference) -> String {
+    anchor_from_parts(position_parts(&position.first_in_range()))
+}
+
+/// Anchor of a structural element or subtitle, e.g. `book_1__chp_3__subtitle_nn1`.
+/// See [`position_anchor`].
+pub fn structural_anchor(path: &[StructuralIdPart]) -> String {
+    anchor_from_parts(path.iter().map(|part| part.to_string()).collect())
+}
+
+/// Anchor of an attachment, e.g. `att_2`. The anchors of its contents are prefixed
+/// with this and `__`, e.g. `att_2__point_3`. See [`position_anchor`].
+pub fn attachment_anchor(identifier: NumericIdentifier) -> String {
+    ElementId {
+        act: Default::default(),
+        structural: Vec::new(),
+        attachment: Some(identifier),
+        position: Reference::default(),
+    }
+    .anchor()
+}
+
+fn anchor_from_parts(parts: Vec<String>) -> String {
+    // Colons are not allowed in XML ids
+    parts.join("__").replace(':', "-")
+}
+
+impl FromStr for ElementId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        let act_str = parts.next().unwrap_or_default();
+        let act = ActIdentifier::from_compact_string(act_str)?;
+        let mut structural = Vec::new();
+        let mut attachment = None;
+        // Collected in the format of Reference::from_compact_string
+        let mut position = ["", "", "", ""];
+        for part in parts {
+            let (kind, value) = part
+                .split_once('_')
+                .ok_or_else(|| anyhow!("Invalid element id part '{part}' in {s}"))?;
+            match kind {
+                "art" => position[0] = value,
+                "para" => position[1] = value,
+                "point" => position[2] = value,
+                "subpoint" => position[3] = value,
+                "att" => attachment = Some(value.parse()?),
+                _ => structural.push(StructuralIdPart::parse(kind, value)?),
+            }
+        }
+        ensure!(
+            structural.is_empty() || attachment.is_none(),
+            "Element id {s} contains both structural and attachment parts"
+        );
+        let position = if position == ["", "", "", ""] {
+            if attachment.is_some() {
+                Reference::default()
+            } else {
+                act.into()
+            }
+        } else {
+            ensure!(
+                structural.is_empty(),
+                "Element id {s} contains both structural and SAE parts"
+            );
+            // Attachments have no articles, their contents are relative to the attachment
+            let act_str = if attachment.is_some() {
+                ensure!(
+                    position[0].is_empty(),
+                    "Element id {s} has an article in an attachment"
+                );
+                ""
+            } else {
+                ensure!(!position[0].is_empty(), "Element id {s} has no article");
+                act_str
+            };
+            Reference::from_compact_string(format!(
+                "{act_str}_{}_{}_{}_{}",
+                position[0].replace(':', "."),
+                position[1],
+                position[2],
+                position[3],
+            ))?
+        };
+        ensure!(
+            position == position.first_in_range(),
+            "Element id {s} is a range"
+        );
+        let result = Self {
+            act,
+            structural,
+            attachment,
+            position,
+        };
+        // Also catches wrong part order, duplicate parts, etc.
+        ensure!(result.to_string() == s, "Non-canonical element id {s}");
+        Ok(result)
+    }
+}
+
+impl From<ElementId> for String {
+    fn from(val: ElementId) -> Self {
+        val.to_string()
+    }
+}
+
+impl TryFrom<String> for ElementId {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl StructuralIdPart {
+    fn parse(kind: &str, value: &str) -> Result<Self> {
+        Ok(match kind {
+            "book" => Self::Book(value.parse()?),
+            "part" => Self::Part(value.parse()?),
+            "title" => Self::Title(value.parse()?),
+            "chp" => Self::Chapter(value.parse()?),
+            "subtitle" => match value.strip_prefix("nn") {
+                Some(index) => Self::UnnumberedSubtitle(index.parse()?),
+                None => Self::Subtitle(value.parse()?),
+            },
+            _ => bail!("Invalid element id part kind '{kind}'"),
+        })
+    }
+
+    /// Structural parts with a lower level contain the ones with higher levels
+    fn level(&self) -> u8 {
+        match self {
+            Self::Book(_) => 0,
+            Self::Part(_) => 1,
+            Self::Title(_) => 2,
+            Self::Chapter(_) => 3,
+            Self::Subtitle(_) | Self::UnnumberedSubtitle(_) => 4,
+        }
+    }
+}
+
+impl Display for StructuralIdPart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Book(id) => write!(f, "book_{id}"),
+            Self::Part(id) => write!(f, "part_{id}"),
+            Self::Title(id) => write!(f, "title_{id}"),
+            Self::Chapter(id) => write!(f, "chp_{id}"),
+            Self::Subtitle(id) => write!(f, "subtitle_{id}"),
+            Self::UnnumberedSubtitle(index) => write!(f, "subtitle_nn{index}"),
+        }
+    }
+}
+
+impl StructuralPath {
+    /// Step to the next child of the act. Returns the path of the child if it is a
+    /// structural element or subtitle, and None for articles.
+    pub fn step(&mut self, child: &ActChild) -> Option<&[StructuralIdPart]> {
+        let part = match child {
+            ActChild::StructuralElement(se) => {
+                self.unnumbered_subtitles = 0;
+                match se.element_type {
+                    StructuralElementType::Book => StructuralIdPart::Book(se.identifier),
+                    StructuralElementType::Part { .. } => StructuralIdPart::Part(se.identifier),
+                    StructuralElementType::Title => StructuralIdPart::Title(se.identifier),
+                    StructuralElementType::Chapter => StructuralIdPart::Chapter(se.identifier),
+                }
+            }
+            ActChild::Subtitle(st) => match st.identifier {
+                Some(identifier) => StructuralIdPart::Subtitle(identifier),
+                None => {
+                    self.unnumbered_subtitles += 1;
+                    StructuralIdPart::UnnumberedSubtitle(self.unnumbered_subtitles)
+                }
+            },
+            ActChild::Article(_) => return None,
+        };
+        self.path.retain(|p| p.level() < part.level());
+        self.path.push(part);
+        Some(&self.path)
+    }
+}
+
+impl Act {
+    /// All structural elements, subtitles, articles, SAEs and attachments of the act
+    /// with their ids, in document order.
+    ///
+    /// Default paragraphs are not returned separately, as they have the same
+    /// id as their article. Block amendment contents are not included.
+    pub fn elements(&self) -> Result<Vec<(ElementId, ElementRef<'_>)>> {
+        let mut result = Vec::new();
+        let mut structural_path = StructuralPath::default();
+        for child in &self.children {
+            let element = match child {
+                ActChild::StructuralElement(se) => ElementRef::StructuralElement(se),
+                ActChild::Subtitle(st) => ElementRef::Subtitle(st),
+                ActChild::Article(article) => {
+                    let position = article.reference().relative_to(&self.reference())?;
+                    let id = ElementId::from_reference(&position)?;
+                    result.push((id.clone(), ElementRef::Article(article)));
+                    for paragraph in &article.children {
+                        paragraph.collect_elements(&id, &mut result)?;
+                    }
+                    continue;
+                }
+            };
+            if let Some(path) = structural_path.step(child) {
+                result.push((
+                    ElementId {
+                        act: self.identifier,
+                        structural: path.to_vec(),
+                        attachment: None,
+                        position: self.reference(),
+                    },
+                    element,
+                ));
+            }
+        }
+        for attachment in &self.attachments {
+            let id = ElementId {
+                act: self.identifier,
+                structural: Vec::new(),
+                attachment: Some(attachment.identifier),
+                position: Reference::default(),
+            };
+            result.push((id.clone(), ElementRef::Attachment(attachment)));
+            if let Some(children) = &attachment.children {
+                children.collect_elements(&id, &mut result)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// All elements of the act by their ids. Use this instead of [`Act::get_element`]
+    /// if many elements are looked up.
+    pub fn element_index(&self) -> Result<BTreeMap<ElementId, ElementRef<'_>>> {
+        Ok(self.elements()?.into_iter().collect())
+    }
+
+    /// Find an element by its id. Returns None if the id belongs to a different act,
+    /// or the element does not exist.
+    ///
+    /// All elements are enumerated for every call: use [`Act::element_index`] for
+    /// repeated lookups.
+    pub fn get_element(&self, id: &ElementId) -> Result<Option<ElementRef<'_>>> {
+        if id.act != self.identifier {
+            return Ok(None);
+        }
+        Ok(self
+            .elements()?
+            .into_iter()
+            .find(|(element_id, _)| element_id == id)
+            .map(|(_, element)| element))
+    }
+}
+
+trait CollectElements<'a> {
+    /// The act, attachment and position of the children are based on the id of the parent
+    fn collect_elements(
+        &'a self,
+        parent: &ElementId,
+        result: &mut Vec<(ElementId, ElementRef<'a>)>,
+    ) -> Result<()>;
+}
+
+impl<'a, IT, CT> CollectElements<'a> for SubArticleElement<IT, CT>
+where
+    SubArticleElement<IT, CT>: ReferenceToElement,
+    &'a SubArticleElement<IT, CT>: Into<ElementRef<'a>>,
+    IT: IdentifierCommon + 'a,
+    CT: ChildrenCommon + CollectElements<'a> + 'a,
+{
+    fn collect_elements(
+        &'a self,
+        parent: &ElementId,
+        result: &mut Vec<(ElementId, ElementRef<'a>)>,
+    ) -> Result<()> {
+        let id = ElementId {
+            position: self.reference().relative_to(&parent.position)?,
+            ..parent.clone()
+        };
+        if !self.identifier.is_empty() {
+            result.push((id.clone(), self.into()));
+        }
+        if let SAEBody::Children { children, .. } = &self.body {
+            children.collect_elements(&id, result)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T: CollectElements<'a>> CollectElements<'a> for Vec<T> {
+    fn collect_elements(
+        &'a self,
+        parent: &ElementId,
+        result: &mut Vec<(ElementId, ElementRef<'a>)>,
+    ) -> Result<()> {
+        for element in self {
+            element.collect_elements(parent, result)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> CollectElements<'a> for ParagraphChildren {
+    fn collect_elements(
+        &'a self,
+        parent: &ElementId,
+        result: &mut Vec<(ElementId, ElementRef<'a>)>,
+    ) -> Result<()> {
+        match self {
+            ParagraphChildren::AlphabeticPoint(x) => x.collect_elements(parent, result),
+            ParagraphChildren::NumericPoint(x) => x.collect_elements(parent, result),
+            // Quoted elements are not part of this act
+            ParagraphChildren::QuotedBlock(_)
+            | ParagraphChildren::BlockAmendment(_)
+            | ParagraphChildren::StructuralBlockAmendment(_) => Ok(()),
+        }
+    }
+}
+
+impl<'a> CollectElements<'a> for AlphabeticPointChildren {
+    fn collect_elements(
+        &'a self,
+        parent: &ElementId,
+        result: &mut Vec<(ElementId, ElementRef<'a>)>,
+    ) -> Result<()> {
+        match self {
+            AlphabeticPointChildren::AlphabeticSubpoint(x) => x.collect_elements(parent, result),
+            AlphabeticPointChildren::NumericSubpoint(x) => x.collect_elements(parent, result),
+        }
+    }
+}
+
+impl<'a> CollectElements<'a> for NumericPointChildren {
+    fn collect_elements(
+        &'a self,
+        parent: &ElementId,
+        result: &mut Vec<(ElementId, ElementRef<'a>)>,
+    ) -> Result<()> {
+        match self {
+            NumericPointChildren::AlphabeticSubpoint(x) => x.collect_elements(parent, result),
+        }
+    }
+}
+
+impl<'a> CollectElements<'a> for AlphabeticSubpointChildren {
+    fn collect_elements(
+        &'a self,
+        _parent: &ElementId,
+        _result: &mut Vec<(ElementId, ElementRef<'a>)>,
+    ) -> Result<()> {
+        // This is an empty enum, the function shall never run.
+        match *self {}
+    }
+}
+
+impl<'a> CollectElements<'a> for NumericSubpointChildren {
+    fn collect_elements(
+        &'a self,
+        _parent: &ElementId,
+        _result: &mut Vec<(ElementId, ElementRef<'a>)>,
+    ) -> Result<()> {
+        // This is an empty enum, the function shall never run.
+        match *self {}
+    }
+}
+
+macro_rules! element_ref_from {
+    ($t:ident) => {
+        impl<'a> From<&'a $t> for ElementRef<'a> {
+            fn from(val: &'a $t) -> Self {
+                ElementRef::$t(val)
+            }
+        }
+    };
+}
+
+element_ref_from!(Paragraph);
+element_ref_from!(AlphabeticPoint);
+element_ref_from!(NumericPoint);
+element_ref_from!(AlphabeticSubpoint);
+element_ref_from!(NumericSubpoint);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::util::singleton_yaml;
+
+    const TEST_ACT: &str = r#"
+        identifier:
+          year: 2012
+          number: 100
+        subject: A tesztelésről
+        preamble: ""
+        publication_date: 2012-07-13
+        children:
+        - StructuralElement:
+            identifier: "1"
+            title: Első könyv
+            element_type: Book
+        - StructuralElement:
+            identifier: "1"
+            title: Általános rendelkezések
+            element_type: Chapter
+        - Subtitle:
+            title: Fogalmak
+        - Article:
+            identifier: "1:1"
+            children:
+            - body:
+                intro: "E törvény alkalmazásában"
+                children:
+                  AlphabeticPoint:
+                  - identifier: a
+                    body: "teszt,"
+                  - identifier: b
+                    body:
+                      intro: "próba:"
+                      children:
+                        NumericSubpoint:
+                        - identifier: "1"
+                          body: "ellenőrzés,"
+                      wrap_up: "együttesen."
+        - Subtitle:
+            identifier: "2"
+            title: Hatály
+        - Article:
+            identifier: "1:2"
+            children:
+            - identifier: "1"
+              body: "Első bekezdés."
+            - identifier: "2"
+              body:
+                intro: "A Ptk. 1:3. § (2) bekezdése helyébe a következő rendelkezés lép:"
+                children:
+                  BlockAmendment:
+                    children:
+                      Paragraph:
+                      - identifier: "2"
+                        body: "Új szöveg."
+        - StructuralElement:
+            identifier: "2"
+            title: Második fejezet
+            element_type: Chapter
+        - Subtitle:
+            title: Záró rendelkezések
+        attachments:
+        - identifier: "1"
+          lines:
+          - 1. Előkészítés
+          children:
+            NumericPoint:
+            - identifier: "1"
+              body: Előkészítés
+        - identifier: "2"
+          lines:
+          - Táblázat
+    "#;
+
+    #[test]
+    fn test_elements() {
+        let act: Act = singleton_yaml::from_str(TEST_ACT).unwrap();
+        let ids: Vec<String> = act
+            .elements()
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id.to_string())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "2012.100/book_1",
+                "2012.100/book_1/chp_1",
+                "2012.100/book_1/chp_1/subtitle_nn1",
+                "2012.100/art_1:1",
+                "2012.100/art_1:1/point_a",
+                "2012.100/art_1:1/point_b",
+                "2012.100/art_1:1/point_b/subpoint_1",
+                "2012.100/book_1/chp_1/subtitle_2",
+                "2012.100/art_1:2",
+                "2012.100/art_1:2/para_1",
+                "2012.100/art_1:2/para_2",
+                "2012.100/book_1/chp_2",
+                "2012.100/book_1/chp_2/subtitle_nn1",
+                "2012.100/att_1",
+                "2012.100/att_1/point_1",
+                "2012.100/att_2",
+            ]
+        );
+
+        let id: ElementId = "2012.100/art_1:1/point_b/subpoint_1".parse().unwrap();
+        match act.get_element(&id).unwrap() {
+            Some(ElementRef::NumericSubpoint(subpoint)) => {
+                assert_eq!(subpoint.body, SAEBody::Text("ellenőrzés,".to_owned()))
+            }
+            x => panic!("Unexpected element {x:?}"),
+        }
+        let id: ElementId = "2012.100/book_1/chp_2/subtitle_nn1".parse().unwrap();
+        match act.get_element(&id).unwrap() {
+            Some(ElementRef::Subtitle(subtitle)) => {
+                assert_eq!(subtitle.title, "Záró rendelkezések")
+            }
+            x => panic!("Unexpected element {x:?}"),
+        }
+        let id: ElementId = "2012.100/art_1:2/para_3".parse().unwrap();
+        assert!(act.get_element(&id).unwrap().is_none());
+        let id: ElementId = "2012.100/att_1/point_1".parse().unwrap();
+        match act.get_element(&id).unwrap() {
+            Some(ElementRef::NumericPoint(point)) => {
+                assert_eq!(point.body, SAEBody::Text("Előkészítés".to_owned()))
+            }
+            x => panic!("Unexpected element {x:?}"),
+        }
+
+        let index = act.element_index().unwrap();
+        assert_eq!(index.len(), ids.len());
+        let id: ElementId = "2012.100/book_1/chp_1/subtitle_2".parse().unwrap();
+        match index.get(&id) {
+            Some(ElementRef::Subtitle(subtitle)) => assert_eq!(subtitle.title, "Hatály"),
+            x => panic!("Unexpected element {x:?}"),
+        }
+    }
+
+    #[test]
+    fn test_anchor() {
+        for (id, anchor) in [
+            (
+                "2012.100/art_3:15/para_2/point_a",
+                "art_3-15__para_2__point_a",
+            ),
+            (
+                "2012.100/art_5a/point_1/subpoint_ab",
+                "art_5a__point_1__subpoint_ab",
+            ),
+            (
+                "2013.5/book_1/part_2/chp_4/subtitle_nn2",
+                "book_1__part_2__chp_4__subtitle_nn2",
+            ),
+            ("2012.100/att_2", "att_2"),
+            ("2012.100/att_2/point_3", "att_2__point_3"),
+        ] {
+            assert_eq!(id.parse::<ElementId>().unwrap().anchor(), anchor);
+        }
+        // Relative positions, e.g. in quoted structures
+        assert_eq!(
+            position_anchor(&Reference::from_compact_string("_1.3_2__").unwrap()),
+            "art_1-3__para_2"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        for s in [
+            "2012.100",
+            "2012.100/art_3:15/para_2/point_a",
+            "2012.100/art_5a/point_1/subpoint_ab",
+            "2013.123.R.4.11.Korm./art_2/para_1",
+            "2013.5/book_1/part_2/title_3/chp_4/subtitle_5",
+            "2013.5/part_1/subtitle_nn2",
+            "2012.100/att_2",
+            "2012.100/att_2/point_3/subpoint_a",
+        ] {
+            let id: ElementId = s.parse().unwrap();
+            assert_eq!(id.to_string(), s);
+            let json = serde_json::to_string(&id).unwrap();
+            assert_eq!(serde_json::from_str::<ElementId>(&json).unwrap(), id);
+        }
+        for s in [
+            "",
+            "2012.100/",
+            "2012.100/para_2",
+            "2012.100/para_2/art_1",
+            "2012.100/art_1-3",
+            "2012.100/chp_1/art_1",
+            "2012.100/chp_I",
+            "2012.100/sect_1",
+            "2012.100/att_1/art_1",
+            "2012.100/chp_1/att_1",
+            "2012.100/point_1/att_1",
+        ] {
+            assert!(s.parse::<ElementId>().is_err(), "{s} should not parse");
+        }
+
+        let reference = ElementId::from_reference(
+            &Reference::from_compact_string("2012.100_3.15_2_a_").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(reference.to_string(), "2012.100/art_3:15/para_2/point_a");
+        assert_eq!(
+            reference.reference(),
+            Some(Reference::from_compact_string("2012.100_3.15_2_a_").unwrap())
+        );
+    }
+}
@@ -57,7 +57,7 @@ impl Fixups {
     pub fn load_from(act_id: ActIdentifier, base_dir: PathBuf) -> Result<Self> {
         let fixup_path = base_dir
             .join(act_id.year.to_string())
-            .join(format!("{act_id}.yml"));
+            .join(format!("{}.yml", act_id.file_name_stem()));
         let fixups = if fixup_path.exists() {
             singleton_yaml::from_reader(File::open(&fixup_path)?)?
         } else {
@@ -15,30 +15,82 @@
 // along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
 
 use std::{
+    cmp::Ordering,
     fmt::{Debug, Display},
     str::FromStr,
 };
 
-use anyhow::{anyhow, Error, Result};
+use anyhow::{anyhow, ensure, Error, Result};
 use lazy_regex::regex_captures;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::util::compact_string::CompactString;
 
+/// Identifier of a legal document published in the Magyar Közlöny.
+///
+/// Despite the name, this is not only used for Acts (törvények), but also for
+/// decrees and resolutions. Those are identified by the issuer and the number
+/// together, e.g. "123/2013. (IV. 11.) Korm. rendelet".
 #[derive(Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ActIdentifier {
     pub year: i16,
     pub number: i32,
+    #[serde(default, skip_serializing_if = "DocumentKind::is_act")]
+    pub kind: DocumentKind,
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum DocumentKind {
+    /// Törvény
+    #[default]
+    Act,
+    /// Rendelet. Month and day are the publication date in the identifier.
+    Decree { issuer: Issuer, month: u8, day: u8 },
+    /// Határozat. Month and day are the publication date in the identifier.
+    Resolution { issuer: Issuer, month: u8, day: u8 },
+}
+
+/// The abbreviated name of the issuer of a decree or resolution, e.g. "Korm." or "BM"
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Issuer {
+    len: u8,
+    bytes: [u8; Issuer::MAX_LEN],
+}
+
+impl ActIdentifier {
+    pub fn is_act(&self) -> bool {
+        self.kind.is_act()
+    }
+
+    /// The long form of the identifier, usable as a file name (decree numbers contain a '/')
+    pub fn file_name_stem(&self) -> String {
+        self.to_string().replace('/', "_")
+    }
 }
 
 impl Display for ActIdentifier {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{:?}. évi {}. törvény",
-            self.year,
-            roman::to(self.number).unwrap()
-        )
+        match self.kind {
+            DocumentKind::Act => write!(
+                f,
+                "{:?}. évi {}. törvény",
+                self.year,
+                roman::to(self.number).unwrap()
+            ),
+            DocumentKind::Decree { issuer, month, day }
+            | DocumentKind::Resolution { issuer, month, day } => write!(
+                f,
+                "{}/{:?}. ({}. {}.) {} {}",
+                self.number,
+                self.year,
+                roman::to(month.into()).unwrap(),
+                day,
+                issuer,
+                self.kind.name(),
+            ),
+        }
     }
 }
 
@@ -57,6 +109,21 @@ impl FromStr for ActIdentifier {
             Some(ActIdentifier {
                 year: year.parse().ok()?,
                 number: roman::from(number)?,
+                kind: DocumentKind::Act,
+            })
+        }
+        fn try_decree(s: &str) -> Option<ActIdentifier> {
+            let (_, number, year, month, day, issuer, name) = regex_captures!(
+                r"([0-9]+)/([0-9]{4})\. \(([IVX]+)\. ([0-9]{1,2})\.\) (\S+) (rendelet|határozat)",
+                s
+            )?;
+            let issuer = issuer.parse().ok()?;
+            let month = roman::from(month)?.try_into().ok()?;
+            let day = day.parse().ok()?;
+            Some(ActIdentifier {
+                year: year.parse().ok()?,
+                number: number.parse().ok()?,
+                kind: DocumentKind::from_name(name, issuer, month, day)?,
             })
         }
         fn try_decimal(s: &str) -> Option<ActIdentifier> {
@@ -64,9 +131,11 @@ impl FromStr for ActIdentifier {
             Some(ActIdentifier {
                 year: year.parse().ok()?,
                 number: number.parse().ok()?,
+                kind: DocumentKind::Act,
             })
         }
         try_classic(s)
+            .or_else(|| try_decree(s))
             .or_else(|| try_decimal(s))
             .ok_or_else(|| anyhow!("Unknown act identifier format: {s}"))
     }
@@ -74,22 +143,153 @@ impl FromStr for ActIdentifier {
 
 impl CompactString for ActIdentifier {
     fn fmt_compact_string(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}.{}", self.year, self.number)
+        write!(f, "{}.{}", self.year, self.number)?;
+        match self.kind {
+            DocumentKind::Act => Ok(()),
+            DocumentKind::Decree { issuer, month, day } => {
+                write!(f, ".R.{month}.{day}.{issuer}")
+            }
+            DocumentKind::Resolution { issuer, month, day } => {
+                write!(f, ".H.{month}.{day}.{issuer}")
+            }
+        }
     }
 
     fn from_compact_string(s: impl AsRef<str>) -> Result<Self> {
         let s = s.as_ref();
-        if let Some((year, number)) = s.split_once('.') {
-            Ok(Self {
+        // The issuer is last, because it may contain dots
+        let parts: Vec<&str> = s.splitn(6, '.').collect();
+        match parts.as_slice() {
+            [year, number] => Ok(Self {
                 year: year.parse()?,
                 number: number.parse()?,
-            })
+                kind: DocumentKind::Act,
+            }),
+            [year, number, kind, month, day, issuer] => {
+                let issuer = issuer.parse()?;
+                let month = month.parse()?;
+                let day = day.parse()?;
+                let kind = match *kind {
+                    "R" => DocumentKind::Decree { issuer, month, day },
+                    "H" => DocumentKind::Resolution { issuer, month, day },
+                    _ => return Err(anyhow!("Invalid document kind in compact act string: {s}")),
+                };
+                Ok(Self {
+                    year: year.parse()?,
+                    number: number.parse()?,
+                    kind,
+                })
+            }
+            _ => Err(anyhow!("Invalid compact act string: {s}")),
+        }
+    }
+}
+
+impl DocumentKind {
+    pub fn is_act(&self) -> bool {
+        matches!(self, DocumentKind::Act)
+    }
+
+    /// The issuer of decrees and resolutions. None for Acts.
+    pub fn issuer(&self) -> Option<Issuer> {
+        match self {
+            DocumentKind::Act => None,
+            DocumentKind::Decree { issuer, .. } | DocumentKind::Resolution { issuer, .. } => {
+                Some(*issuer)
+            }
+        }
+    }
+
+    /// The hungarian name of the document type, as used in identifiers
+    pub fn name(&self) -> &'static str {
+        match self {
+            DocumentKind::Act => "törvény",
+            DocumentKind::Decree { .. } => "rendelet",
+            DocumentKind::Resolution { .. } => "határozat",
+        }
+    }
+
+    /// Decree or resolution kind from its hungarian name. The name may have
+    /// a suffix, e.g. "rendeletben".
+    pub fn from_name(name: &str, issuer: Issuer, month: u8, day: u8) -> Option<Self> {
+        if name.starts_with("rendelet") {
+            Some(DocumentKind::Decree { issuer, month, day })
+        } else if name.starts_with("határozat") {
+            Some(DocumentKind::Resolution { issuer, month, day })
         } else {
-            Err(anyhow!("Invalid compact act string: {s}"))
+            None
         }
     }
 }
 
+impl Issuer {
+    const MAX_LEN: usize = 15;
+
+    pub fn as_str(&self) -> &str {
+        // Only valid strings are stored, see from_str()
+        std::str::from_utf8(&self.bytes[..self.len.into()]).unwrap()
+    }
+}
+
+impl FromStr for Issuer {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ensure!(
+            !s.is_empty() && s.len() <= Self::MAX_LEN,
+            "Invalid issuer length: {s}"
+        );
+        ensure!(
+            !s.contains(|c: char| c.is_whitespace() || c == '_' || c == '/'),
+            "Invalid character in issuer: {s}"
+        );
+        let mut bytes = [0; Self::MAX_LEN];
+        bytes[..s.len()].copy_from_slice(s.as_bytes());
+        Ok(Self {
+            len: s.len() as u8,
+            bytes,
+        })
+    }
+}
+
+impl Display for Issuer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Debug for Issuer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialOrd for Issuer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Issuer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Serialize for Issuer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Issuer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -103,6 +303,7 @@ mod tests {
             ActIdentifier {
                 year: 2012,
                 number: 123,
+                ..Default::default()
             }
         );
         assert_eq!(
@@ -110,6 +311,7 @@ mod tests {
             ActIdentifier {
                 year: 2012,
                 number: 123,
+                ..Default::default()
             }
         );
         assert_eq!(
@@ -117,6 +319,7 @@ mod tests {
             ActIdentifier {
                 year: 2012,
                 number: 123,
+                ..Default::default()
             }
         );
         assert_eq!(
@@ -124,6 +327,7 @@ mod tests {
             ActIdentifier {
                 year: 2012,
                 number: 123,
+                ..Default::default()
             }
         );
         assert_eq!(
@@ -131,15 +335,51 @@ mod tests {
             ActIdentifier {
                 year: 2012,
                 number: 154,
+                ..Default::default()
             }
         );
         let roundtrip_test = ActIdentifier {
             year: 2022,
             number: 420,
+            ..Default::default()
         };
         assert_eq!(roundtrip_test, roundtrip_test.to_string().parse().unwrap());
     }
 
+    #[test]
+    fn test_decree_identifier_parsing() {
+        let decree: ActIdentifier = "123/2013. (IV. 11.) Korm. rendelet".parse().unwrap();
+        assert_eq!(
+            decree,
+            ActIdentifier {
+                year: 2013,
+                number: 123,
+                kind: DocumentKind::Decree {
+                    issuer: "Korm.".parse().unwrap(),
+                    month: 4,
+                    day: 11
+                },
+            }
+        );
+        assert_eq!(decree.to_string(), "123/2013. (IV. 11.) Korm. rendelet");
+        assert_eq!(decree.kind.issuer().unwrap().as_str(), "Korm.");
+
+        let resolution: ActIdentifier = "1234/2013. (XII. 1.) Korm. határozat".parse().unwrap();
+        assert!(matches!(
+            resolution.kind,
+            DocumentKind::Resolution {
+                month: 12,
+                day: 1,
+                ..
+            }
+        ));
+        assert_eq!(resolution, resolution.to_string().parse().unwrap());
+
+        let ministerial: ActIdentifier = "12/2013. (IV. 11.) BM rendelet".parse().unwrap();
+        assert_eq!(ministerial.number, 12);
+        assert_ne!(ministerial.kind, decree.kind);
+    }
+
     #[test]
     fn test_compact_string() {
         let tst = ActIdentifier::from_str("2012/420").unwrap();
@@ -149,5 +389,17 @@ mod tests {
         assert!(ActIdentifier::from_compact_string("a").is_err());
         assert!(ActIdentifier::from_compact_string("2012.a").is_err());
         assert!(ActIdentifier::from_compact_string("a.420").is_err());
+
+        let decree = ActIdentifier::from_str("123/2013. (IV. 11.) Korm. rendelet").unwrap();
+        assert_eq!(
+            &decree.compact_string().to_string(),
+            "2013.123.R.4.11.Korm."
+        );
+        assert_eq!(
+            decree,
+            ActIdentifier::from_compact_string("2013.123.R.4.11.Korm.").unwrap()
+        );
+        assert!(ActIdentifier::from_compact_string("2013.123.X.4.11.Korm.").is_err());
+        assert!(ActIdentifier::from_compact_string("2013.123.R.4.11").is_err());
     }
 }
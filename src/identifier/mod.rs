@@ -22,7 +22,7 @@ mod paragraph;
 mod prefixed_alphabetic;
 pub mod range;
 
-pub use act::ActIdentifier;
+pub use act::{ActIdentifier, DocumentKind, Issuer};
 pub use alphabetic::{AlphabeticIdentifier, HungarianIdentifierChar};
 pub use article::ArticleIdentifier;
 pub use numeric::NumericIdentifier;
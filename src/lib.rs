@@ -14,12 +14,19 @@
 // You should have received a copy of the GNU General Public License
 // along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
 
+pub mod act_locator;
+pub mod amender;
+pub mod corpus;
+pub mod diff;
+pub mod element_id;
 pub mod fixups;
 pub mod identifier;
 pub mod mk_downloader;
+pub mod mk_index;
 pub mod output;
 pub mod parser;
 pub mod reference;
 pub mod semantic_info;
 pub mod structure;
 pub mod util;
+pub mod validation;
@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
 
+use std::fmt::Display;
 use std::fs;
 use std::str::FromStr;
 use std::{io::Read, path::Path};
@@ -65,6 +66,40 @@ impl FromStr for MkIssue {
     }
 }
 
+impl Display for MkIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.issue)
+    }
+}
+
+/// All MK issues that were already downloaded into `cache_dir`, in order
+pub fn cached_mk_issues(cache_dir: &Path) -> Result<Vec<MkIssue>> {
+    let mut result = Vec::new();
+    let mk_dir = cache_dir.join("MK");
+    if !mk_dir.exists() {
+        return Ok(result);
+    }
+    for year_entry in fs::read_dir(mk_dir)? {
+        let year_entry = year_entry?;
+        let year = match year_entry.file_name().to_str().and_then(|s| s.parse().ok()) {
+            Some(year) => year,
+            None => continue,
+        };
+        for issue_entry in fs::read_dir(year_entry.path())? {
+            let file_name = issue_entry?.file_name();
+            if let Some(issue) = file_name
+                .to_str()
+                .and_then(|s| s.strip_suffix(".pdf"))
+                .and_then(|s| s.parse().ok())
+            {
+                result.push(MkIssue { year, issue });
+            }
+        }
+    }
+    result.sort();
+    Ok(result)
+}
+
 pub fn download_mk_issue(issue: &MkIssue, cache_dir: &Path) -> Result<Vec<u8>> {
     let file_path = cache_dir.join(issue.cache_key());
     if let Ok(cached_result) = fs::read(&file_path) {
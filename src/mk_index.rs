@@ -0,0 +1,157 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeMap, fmt::Display, path::Path, str::FromStr};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    identifier::ActIdentifier,
+    mk_downloader::MkIssue,
+    parser::{mk_act_section::parse_mk_pages_into_acts, pdf::PageOfLines},
+};
+
+/// The MK issue a document was published in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MkIndexRecord {
+    #[serde(serialize_with = "to_str", deserialize_with = "from_str")]
+    pub mk_issue: MkIssue,
+    #[serde(serialize_with = "to_str", deserialize_with = "from_str")]
+    pub act: ActIdentifier,
+    pub title: String,
+    /// Index of the PDF page where the document starts, if known
+    #[serde(default)]
+    pub first_page: Option<usize>,
+    /// Index of the PDF page where the document ends, if known
+    #[serde(default)]
+    pub last_page: Option<usize>,
+}
+
+/// Lookup table from document identifiers to MK issues.
+///
+/// Stored as a CSV file with a header row, ordered by issue. The columns are:
+/// - `mk_issue`: the MK issue, e.g. `2013/52`
+/// - `act`: the identifier of the document
+/// - `title`: the subject of the document
+/// - `first_page` and `last_page`: the PDF page indices of the document. These are
+///   optional: they may be empty, or missing from older files.
+#[derive(Debug, Clone, Default)]
+pub struct MkIndex {
+    records: BTreeMap<ActIdentifier, MkIndexRecord>,
+}
+
+impl MkIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut result = Self::new();
+        for record in csv::Reader::from_path(path)
+            .with_context(|| anyhow!("Error opening {path:?}"))?
+            .deserialize()
+        {
+            result.insert(record.with_context(|| anyhow!("Error parsing {path:?}"))?);
+        }
+        Ok(result)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut records: Vec<&MkIndexRecord> = self.records.values().collect();
+        records.sort_by(|r1, r2| (&r1.mk_issue, r1.act).cmp(&(&r2.mk_issue, r2.act)));
+        let mut writer =
+            csv::Writer::from_path(path).with_context(|| anyhow!("Error creating {path:?}"))?;
+        for record in records {
+            writer.serialize(record)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn get(&self, act: ActIdentifier) -> Option<&MkIndexRecord> {
+        self.records.get(&act)
+    }
+
+    /// Add or replace the record of a document
+    pub fn insert(&mut self, record: MkIndexRecord) -> Option<MkIndexRecord> {
+        self.records.insert(record.act, record)
+    }
+
+    /// Replace all records of `issue` with the documents found in its pages.
+    /// Returns the number of documents found.
+    pub fn add_issue(&mut self, issue: &MkIssue, pages: &[PageOfLines]) -> Result<usize> {
+        let acts = parse_mk_pages_into_acts(pages)?;
+        self.records.retain(|_, record| record.mk_issue != *issue);
+        for act in &acts {
+            self.insert(MkIndexRecord {
+                mk_issue: issue.clone(),
+                act: act.identifier,
+                title: act.subject.clone(),
+                first_page: Some(act.first_page),
+                last_page: Some(act.last_page),
+            });
+        }
+        Ok(acts.len())
+    }
+
+    /// Iterate over all records, ordered by document identifier
+    pub fn iter(&self) -> impl Iterator<Item = &MkIndexRecord> {
+        self.records.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+fn to_str<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+fn from_str<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    <T as FromStr>::Err: std::fmt::Display,
+{
+    let buf = String::deserialize(deserializer)?;
+    T::from_str(&buf).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_and_save() {
+        let index = MkIndex::load(Path::new("data/act_to_mk_issue.csv")).unwrap();
+        let ptk = index.get("2013. évi V. törvény".parse().unwrap()).unwrap();
+        assert_eq!(ptk.mk_issue, "2013/31".parse().unwrap());
+        assert_eq!(ptk.title, "A Polgári Törvénykönyvről");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.csv");
+        index.save(&path).unwrap();
+        let reloaded = MkIndex::load(&path).unwrap();
+        assert_eq!(reloaded.len(), index.len());
+        assert!(reloaded.iter().eq(index.iter()));
+    }
+}
@@ -0,0 +1,957 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Write;
+
+use anyhow::Result;
+
+use crate::{
+    element_id::{attachment_anchor, position_anchor, structural_anchor, StructuralPath},
+    identifier::{ActIdentifier, DocumentKind, IdentifierCommon},
+    reference::{to_element::ReferenceToElement, Reference},
+    semantic_info::{SemanticInfo, SpecialPhrase},
+    structure::{
+        Act, ActChild, AlphabeticPointChildren, AlphabeticSubpointChildren, Article,
+        BlockAmendment, BlockAmendmentChildren, ChildrenCommon, NumericPointChildren,
+        NumericSubpointChildren, ParagraphChildren, QuotedBlock, SAEBody, SAEHeaderString,
+        StructuralBlockAmendment, StructuralElement, StructuralElementType, SubArticleElement,
+        Subtitle,
+    },
+};
+
+const AKN_NAMESPACE: &str = "http://docs.oasis-open.org/legaldocml/ns/akn/3.0";
+
+#[derive(Debug, Clone)]
+pub struct AkomaNtosoParams {
+    /// The act being written. References to it are written as local links.
+    act: ActIdentifier,
+    /// Absolute position of the parent element
+    base: Reference,
+    /// Prepended to all eIds. Used for elements in quoted structures.
+    eid_prefix: String,
+    indentation_level: usize,
+}
+
+pub trait AkomaNtosoOutput {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()>;
+}
+
+impl Act {
+    /// Write the act as an Akoma Ntoso 3.0 XML document
+    pub fn write_as_akoma_ntoso(&self, writer: &mut impl Write) -> Result<()> {
+        let params = AkomaNtosoParams {
+            act: self.identifier,
+            base: self.reference(),
+            eid_prefix: String::new(),
+            indentation_level: 0,
+        };
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        self.write_as_akn(writer, params)
+    }
+}
+
+impl AkomaNtosoOutput for Act {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        params.write_line(writer, &format!(r#"<akomaNtoso xmlns="{AKN_NAMESPACE}">"#))?;
+        let act_params = params.indented();
+        act_params.write_line(
+            writer,
+            &format!(r#"<act name="{}">"#, self.identifier.kind.name()),
+        )?;
+        let inner = act_params.indented();
+        self.write_meta(writer, inner.clone())?;
+
+        inner.write_line(writer, "<preface>")?;
+        inner.indented().write_line(
+            writer,
+            &format!(
+                "<p><docNumber>{}</docNumber></p>",
+                escape(&self.identifier.to_string())
+            ),
+        )?;
+        inner.indented().write_line(
+            writer,
+            &format!("<p><docTitle>{}</docTitle></p>", escape(&self.subject)),
+        )?;
+        inner.write_line(writer, "</preface>")?;
+        if !self.preamble.is_empty() {
+            inner.write_line(writer, "<preamble>")?;
+            inner
+                .indented()
+                .write_line(writer, &format!("<p>{}</p>", escape(&self.preamble)))?;
+            inner.write_line(writer, "</preamble>")?;
+        }
+
+        inner.write_line(writer, "<body>")?;
+        self.children.write_as_akn(writer, inner.indented())?;
+        for attachment in &self.attachments {
+            let attachment_params = inner.indented();
+            attachment_params.write_line(
+                writer,
+                &format!(
+                    r#"<hcontainer eId="{}" name="attachment">"#,
+                    attachment_anchor(attachment.identifier)
+                ),
+            )?;
+            let content_params = attachment_params.indented();
+            content_params.write_line(
+                writer,
+                &format!("<num>{}</num>", escape(&attachment.header_string())),
+            )?;
+            if !attachment.title.is_empty() {
+                content_params.write_line(
+                    writer,
+                    &format!("<heading>{}</heading>", escape(&attachment.title)),
+                )?;
+            }
+            match &attachment.children {
+                Some(children) if !children.is_inline() => {
+                    // Attachment contents have no article, so their positions are not
+                    // relative to the act.
+                    let mut children_params = content_params.with_base(Reference::default());
+                    children_params.eid_prefix =
+                        format!("{}__", attachment_anchor(attachment.identifier));
+                    children.write_as_akn(writer, children_params)?;
+                }
+                _ => {
+                    // Tables and such are kept as they are
+                    content_params.write_line(writer, "<content>")?;
+                    for line in &attachment.lines {
+                        content_params
+                            .indented()
+                            .write_line(writer, &format!("<p>{}</p>", escape(line)))?;
+                    }
+                    content_params.write_line(writer, "</content>")?;
+                }
+            }
+            attachment_params.write_line(writer, "</hcontainer>")?;
+        }
+        inner.write_line(writer, "</body>")?;
+
+        if !self.signatories.is_empty() {
+            inner.write_line(writer, "<conclusions>")?;
+            for (i, signatory) in self.signatories.iter().enumerate() {
+                inner.indented().write_line(
+                    writer,
+                    &format!(
+                        r##"<p><signature><person refersTo="#signatory_{n}">{}</person> <role refersTo="#signatory_{n}_role">{}</role></signature></p>"##,
+                        escape(&signatory.name),
+                        escape(&signatory.role),
+                        n = i + 1,
+                    ),
+                )?;
+            }
+            inner.write_line(writer, "</conclusions>")?;
+        }
+        act_params.write_line(writer, "</act>")?;
+        params.write_line(writer, "</akomaNtoso>")
+    }
+}
+
+impl Act {
+    fn write_meta(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        let work = work_uri(self.identifier);
+        let expression = format!("{work}/hun@{}", self.publication_date);
+        let date = format!(
+            r#"<FRBRdate date="{}" name="publication"/>"#,
+            self.publication_date
+        );
+        let (author, author_name) = author(self.identifier);
+        params.write_line(writer, "<meta>")?;
+        let p1 = params.indented();
+        let p2 = p1.indented();
+        let p3 = p2.indented();
+        p1.write_line(writer, r##"<identification source="#hun_law">"##)?;
+        p2.write_line(writer, "<FRBRWork>")?;
+        p3.write_line(writer, &format!(r#"<FRBRthis value="{work}/!main"/>"#))?;
+        p3.write_line(writer, &format!(r#"<FRBRuri value="{work}"/>"#))?;
+        p3.write_line(writer, &date)?;
+        p3.write_line(writer, &format!(r##"<FRBRauthor href="#{author}"/>"##))?;
+        p3.write_line(writer, r#"<FRBRcountry value="hu"/>"#)?;
+        p3.write_line(
+            writer,
+            &format!(r#"<FRBRnumber value="{}"/>"#, self.identifier.number),
+        )?;
+        p3.write_line(
+            writer,
+            &format!(
+                r#"<FRBRname value="{}"/>"#,
+                escape(&self.identifier.to_string())
+            ),
+        )?;
+        p2.write_line(writer, "</FRBRWork>")?;
+        p2.write_line(writer, "<FRBRExpression>")?;
+        p3.write_line(
+            writer,
+            &format!(r#"<FRBRthis value="{expression}/!main"/>"#),
+        )?;
+        p3.write_line(writer, &format!(r#"<FRBRuri value="{expression}"/>"#))?;
+        p3.write_line(writer, &date)?;
+        p3.write_line(writer, &format!(r##"<FRBRauthor href="#{author}"/>"##))?;
+        p3.write_line(writer, r#"<FRBRlanguage language="hun"/>"#)?;
+        p2.write_line(writer, "</FRBRExpression>")?;
+        p2.write_line(writer, "<FRBRManifestation>")?;
+        p3.write_line(
+            writer,
+            &format!(r#"<FRBRthis value="{expression}/!main.xml"/>"#),
+        )?;
+        p3.write_line(writer, &format!(r#"<FRBRuri value="{expression}.akn"/>"#))?;
+        p3.write_line(writer, &date)?;
+        p3.write_line(writer, r##"<FRBRauthor href="#hun_law"/>"##)?;
+        p2.write_line(writer, "</FRBRManifestation>")?;
+        p1.write_line(writer, "</identification>")?;
+        p1.write_line(
+            writer,
+            &format!(
+                r#"<publication date="{}" name="Magyar Közlöny" showAs="Magyar Közlöny"/>"#,
+                self.publication_date
+            ),
+        )?;
+
+        p1.write_line(writer, r##"<references source="#hun_law">"##)?;
+        p2.write_line(
+            writer,
+            r#"<TLCOrganization eId="hun_law" href="/ontology/organization/hun_law" showAs="Hun-Law"/>"#,
+        )?;
+        p2.write_line(
+            writer,
+            &format!(
+                r#"<TLCOrganization eId="{author}" href="/ontology/organization/hu/{author}" showAs="{}"/>"#,
+                escape(&author_name)
+            ),
+        )?;
+        for (i, signatory) in self.signatories.iter().enumerate() {
+            p2.write_line(
+                writer,
+                &format!(
+                    r#"<TLCPerson eId="signatory_{n}" href="/ontology/person/hu/signatory_{n}" showAs="{}"/>"#,
+                    escape(&signatory.name),
+                    n = i + 1,
+                ),
+            )?;
+            p2.write_line(
+                writer,
+                &format!(
+                    r#"<TLCRole eId="signatory_{n}_role" href="/ontology/role/hu/signatory_{n}" showAs="{}"/>"#,
+                    escape(&signatory.role),
+                    n = i + 1,
+                ),
+            )?;
+        }
+        p1.write_line(writer, "</references>")?;
+
+        if !self.footnotes.is_empty() {
+            p1.write_line(writer, r##"<notes source="#hun_law">"##)?;
+            for (i, footnote) in self.footnotes.iter().enumerate() {
+                p2.write_line(
+                    writer,
+                    &format!(
+                        r#"<note eId="note_{}" marker="{}"><p>{}</p></note>"#,
+                        i + 1,
+                        escape(&footnote.marker),
+                        escape(&footnote.text),
+                    ),
+                )?;
+            }
+            p1.write_line(writer, "</notes>")?;
+        }
+        params.write_line(writer, "</meta>")
+    }
+}
+
+/// Structural elements are only bookmarks in the act, but they are containers in
+/// Akoma Ntoso. Containers are closed when an element of the same or higher level comes.
+impl AkomaNtosoOutput for Vec<ActChild> {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        // Level and closing tag of the open containers
+        let mut open_containers: Vec<(u8, &str)> = Vec::new();
+        let mut structural_path = StructuralPath::default();
+        for child in self {
+            let container = match child {
+                ActChild::StructuralElement(se) => {
+                    let tag = match se.element_type {
+                        StructuralElementType::Book => "book",
+                        StructuralElementType::Part { .. } => "part",
+                        StructuralElementType::Title => "title",
+                        StructuralElementType::Chapter => "chapter",
+                    };
+                    Some((structural_level(se.element_type), tag))
+                }
+                ActChild::Subtitle(_) => Some((SUBTITLE_LEVEL, "subtitle")),
+                ActChild::Article(_) => None,
+            };
+            let path = structural_path.step(child);
+            if let (Some((level, tag)), Some(path)) = (container, path) {
+                while let Some((open_level, open_tag)) = open_containers.last() {
+                    if *open_level < level {
+                        break;
+                    }
+                    params
+                        .indented_by(open_containers.len() - 1)
+                        .write_line(writer, &format!("</{open_tag}>"))?;
+                    open_containers.pop();
+                }
+                let eid = format!("{}{}", params.eid_prefix, structural_anchor(path));
+                params
+                    .indented_by(open_containers.len())
+                    .write_line(writer, &format!(r#"<{tag} eId="{}">"#, escape(&eid)))?;
+                open_containers.push((level, tag));
+            }
+            child.write_as_akn(writer, params.indented_by(open_containers.len()))?;
+        }
+        while let Some((_, open_tag)) = open_containers.pop() {
+            params
+                .indented_by(open_containers.len())
+                .write_line(writer, &format!("</{open_tag}>"))?;
+        }
+        Ok(())
+    }
+}
+
+const SUBTITLE_LEVEL: u8 = 4;
+
+fn structural_level(element_type: StructuralElementType) -> u8 {
+    match element_type {
+        StructuralElementType::Book => 0,
+        StructuralElementType::Part { .. } => 1,
+        StructuralElementType::Title => 2,
+        StructuralElementType::Chapter => 3,
+    }
+}
+
+/// Writes the contents of the container. The container itself is opened and
+/// closed by the list of act children.
+impl AkomaNtosoOutput for ActChild {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        match self {
+            ActChild::StructuralElement(x) => x.write_as_akn(writer, params),
+            ActChild::Subtitle(x) => x.write_as_akn(writer, params),
+            ActChild::Article(x) => x.write_as_akn(writer, params),
+        }
+    }
+}
+
+impl AkomaNtosoOutput for StructuralElement {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        params.write_line(
+            writer,
+            &format!("<num>{}</num>", escape(&self.header_string()?)),
+        )?;
+        if !self.title.is_empty() {
+            params.write_line(
+                writer,
+                &format!("<heading>{}</heading>", escape(&self.title)),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl AkomaNtosoOutput for Subtitle {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        if let Some(identifier) = self.identifier {
+            params.write_line(writer, &format!("<num>{}.</num>", identifier.with_slash()))?;
+        }
+        params.write_line(
+            writer,
+            &format!("<heading>{}</heading>", escape(&self.title)),
+        )
+    }
+}
+
+impl AkomaNtosoOutput for Article {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        let position = self.reference().relative_to(&params.base)?;
+        params.write_line(
+            writer,
+            &format!(r#"<article eId="{}">"#, params.eid(&position)),
+        )?;
+        let mut inner = params.indented();
+        inner.write_line(
+            writer,
+            &format!("<num>{}</num>", escape(&self.header_string())),
+        )?;
+        if let Some(title) = &self.title {
+            inner.write_line(writer, &format!("<heading>{}</heading>", escape(title)))?;
+        }
+        inner.base = position;
+        self.children.write_as_akn(writer, inner)?;
+        params.write_line(writer, "</article>")
+    }
+}
+
+/// Children of SAEs
+pub trait AkomaNtosoChildren: AkomaNtosoOutput {
+    /// The tag of the SAE containing these children
+    const PARENT_TAG: &'static str;
+
+    /// Children which are written inline, inside the content of their parent
+    fn is_inline(&self) -> bool {
+        false
+    }
+}
+
+impl<IT, CT> AkomaNtosoOutput for SubArticleElement<IT, CT>
+where
+    SubArticleElement<IT, CT>: SAEHeaderString + ReferenceToElement,
+    IT: IdentifierCommon,
+    CT: ChildrenCommon + AkomaNtosoChildren,
+{
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        let position = self.reference().relative_to(&params.base)?;
+        let eid = params.eid(&position);
+        // The only paragraph of an article has no identifier, and it has the same
+        // position as the article, so it is merged into it.
+        let has_own_element = !self.identifier.is_empty();
+        let tag = CT::PARENT_TAG;
+        let mut inner = params.clone();
+        if has_own_element {
+            params.write_line(writer, &format!(r#"<{tag} eId="{eid}">"#))?;
+            inner = inner.indented();
+            inner.write_line(
+                writer,
+                &format!("<num>{}</num>", escape(&self.header_string())),
+            )?;
+        }
+        let text_params = inner.with_base(position.clone());
+        let is_modification = matches!(
+            self.semantic_info.special_phrase,
+            Some(
+                SpecialPhrase::BlockAmendment(_)
+                    | SpecialPhrase::StructuralBlockAmendment(_)
+                    | SpecialPhrase::TextAmendment(_)
+                    | SpecialPhrase::Repeal(_)
+            )
+        );
+        let (mod_start, mod_end) = if is_modification {
+            (format!(r#"<mod eId="{eid}__mod_1">"#), "</mod>")
+        } else {
+            (String::new(), "")
+        };
+        match &self.body {
+            SAEBody::Text(text) => {
+                inner.write_line(writer, "<content>")?;
+                inner.indented().write_line(
+                    writer,
+                    &format!(
+                        "<p>{mod_start}{}{mod_end}</p>",
+                        text_params.inline_text(text, &self.semantic_info, &eid)?
+                    ),
+                )?;
+                inner.write_line(writer, "</content>")?;
+            }
+            SAEBody::Children {
+                intro,
+                children,
+                wrap_up,
+            } if children.is_inline() => {
+                inner.write_line(writer, "<content>")?;
+                let content_params = inner.indented();
+                content_params.write_line(
+                    writer,
+                    &format!(
+                        "<p>{mod_start}{}",
+                        text_params.inline_text(intro, &self.semantic_info, &eid)?
+                    ),
+                )?;
+                // Quoted structures are relative to the amended element
+                let mut children_params = content_params.indented();
+                children_params.eid_prefix = format!("{eid}__mod_1__qstr_1__");
+                children_params.base = match &self.semantic_info.special_phrase {
+                    Some(SpecialPhrase::BlockAmendment(ba)) => {
+                        ba.position.relative_to(&position)?.parent()
+                    }
+                    Some(SpecialPhrase::StructuralBlockAmendment(sba)) => {
+                        sba.position.act.unwrap_or(params.act).into()
+                    }
+                    _ => Reference::default(),
+                };
+                children.write_as_akn(writer, children_params)?;
+                content_params.write_line(
+                    writer,
+                    &format!(
+                        "{}{mod_end}</p>",
+                        escape(wrap_up.as_deref().unwrap_or_default())
+                    ),
+                )?;
+                inner.write_line(writer, "</content>")?;
+            }
+            SAEBody::Children {
+                intro,
+                children,
+                wrap_up,
+            } => {
+                inner.write_line(
+                    writer,
+                    &format!(
+                        "<intro><p>{mod_start}{}{mod_end}</p></intro>",
+                        text_params.inline_text(intro, &self.semantic_info, &eid)?
+                    ),
+                )?;
+                children.write_as_akn(writer, text_params.clone())?;
+                if let Some(wrap_up) = wrap_up {
+                    inner.write_line(
+                        writer,
+                        &format!("<wrapUp><p>{}</p></wrapUp>", escape(wrap_up)),
+                    )?;
+                }
+            }
+        }
+        if has_own_element {
+            params.write_line(writer, &format!("</{tag}>"))?;
+        }
+        Ok(())
+    }
+}
+
+impl AkomaNtosoOutput for ParagraphChildren {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        match self {
+            ParagraphChildren::AlphabeticPoint(x) => x.write_as_akn(writer, params),
+            ParagraphChildren::NumericPoint(x) => x.write_as_akn(writer, params),
+            ParagraphChildren::QuotedBlock(x) => x.write_as_akn(writer, params),
+            ParagraphChildren::BlockAmendment(x) => x.write_as_akn(writer, params),
+            ParagraphChildren::StructuralBlockAmendment(x) => x.write_as_akn(writer, params),
+        }
+    }
+}
+
+impl AkomaNtosoChildren for ParagraphChildren {
+    const PARENT_TAG: &'static str = "paragraph";
+
+    fn is_inline(&self) -> bool {
+        matches!(
+            self,
+            ParagraphChildren::QuotedBlock(_)
+                | ParagraphChildren::BlockAmendment(_)
+                | ParagraphChildren::StructuralBlockAmendment(_)
+        )
+    }
+}
+
+impl AkomaNtosoOutput for AlphabeticPointChildren {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        match self {
+            AlphabeticPointChildren::AlphabeticSubpoint(x) => x.write_as_akn(writer, params),
+            AlphabeticPointChildren::NumericSubpoint(x) => x.write_as_akn(writer, params),
+        }
+    }
+}
+
+impl AkomaNtosoChildren for AlphabeticPointChildren {
+    const PARENT_TAG: &'static str = "point";
+}
+
+impl AkomaNtosoOutput for NumericPointChildren {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        match self {
+            NumericPointChildren::AlphabeticSubpoint(x) => x.write_as_akn(writer, params),
+        }
+    }
+}
+
+impl AkomaNtosoChildren for NumericPointChildren {
+    const PARENT_TAG: &'static str = "point";
+}
+
+impl AkomaNtosoOutput for AlphabeticSubpointChildren {
+    fn write_as_akn(&self, _writer: &mut impl Write, _params: AkomaNtosoParams) -> Result<()> {
+        // This is an empty enum, the function shall never run.
+        match *self {}
+    }
+}
+
+impl AkomaNtosoChildren for AlphabeticSubpointChildren {
+    const PARENT_TAG: &'static str = "point";
+}
+
+impl AkomaNtosoOutput for NumericSubpointChildren {
+    fn write_as_akn(&self, _writer: &mut impl Write, _params: AkomaNtosoParams) -> Result<()> {
+        // This is an empty enum, the function shall never run.
+        match *self {}
+    }
+}
+
+impl AkomaNtosoChildren for NumericSubpointChildren {
+    const PARENT_TAG: &'static str = "point";
+}
+
+impl AkomaNtosoOutput for Vec<QuotedBlock> {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        for quoted_block in self {
+            let mut text = String::new();
+            if let Some(intro) = &quoted_block.intro {
+                text.push_str(&escape(intro));
+            }
+            text.push_str(r#"<quotedText startQuote="„" endQuote="”">"#);
+            let lines: Vec<String> = quoted_block
+                .lines
+                .iter()
+                .map(|line| escape(line.content()))
+                .collect();
+            text.push_str(&lines.join("<br/>"));
+            text.push_str("</quotedText>");
+            if let Some(wrap_up) = &quoted_block.wrap_up {
+                text.push_str(&escape(wrap_up));
+            }
+            params.write_line(writer, &text)?;
+        }
+        Ok(())
+    }
+}
+
+impl AkomaNtosoOutput for BlockAmendment {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        write_quoted_structure(
+            writer,
+            params,
+            self.intro.as_deref(),
+            self.wrap_up.as_deref(),
+            |writer, params| self.children.write_as_akn(writer, params),
+        )
+    }
+}
+
+impl AkomaNtosoOutput for StructuralBlockAmendment {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        write_quoted_structure(
+            writer,
+            params,
+            self.intro.as_deref(),
+            self.wrap_up.as_deref(),
+            |writer, params| self.children.write_as_akn(writer, params),
+        )
+    }
+}
+
+fn write_quoted_structure<W: Write>(
+    writer: &mut W,
+    params: AkomaNtosoParams,
+    intro: Option<&str>,
+    wrap_up: Option<&str>,
+    write_children: impl FnOnce(&mut W, AkomaNtosoParams) -> Result<()>,
+) -> Result<()> {
+    let eid = params.eid_prefix.trim_end_matches('_');
+    params.write_line(
+        writer,
+        &format!(r#"<quotedStructure eId="{eid}" startQuote="„" endQuote="”">"#),
+    )?;
+    let inner = params.indented();
+    if let Some(intro) = intro {
+        inner.write_line(writer, &format!("<p>{}</p>", escape(intro)))?;
+    }
+    write_children(writer, inner.clone())?;
+    if let Some(wrap_up) = wrap_up {
+        inner.write_line(writer, &format!("<p>{}</p>", escape(wrap_up)))?;
+    }
+    params.write_line(writer, "</quotedStructure>")
+}
+
+impl AkomaNtosoOutput for BlockAmendmentChildren {
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        match self {
+            BlockAmendmentChildren::Paragraph(x) => x.write_as_akn(writer, params),
+            BlockAmendmentChildren::AlphabeticPoint(x) => x.write_as_akn(writer, params),
+            BlockAmendmentChildren::NumericPoint(x) => x.write_as_akn(writer, params),
+            BlockAmendmentChildren::AlphabeticSubpoint(x) => x.write_as_akn(writer, params),
+            BlockAmendmentChildren::NumericSubpoint(x) => x.write_as_akn(writer, params),
+        }
+    }
+}
+
+impl<IT, CT> AkomaNtosoOutput for Vec<SubArticleElement<IT, CT>>
+where
+    SubArticleElement<IT, CT>: AkomaNtosoOutput,
+    IT: IdentifierCommon,
+    CT: ChildrenCommon,
+{
+    fn write_as_akn(&self, writer: &mut impl Write, params: AkomaNtosoParams) -> Result<()> {
+        for element in self {
+            element.write_as_akn(writer, params.clone())?;
+        }
+        Ok(())
+    }
+}
+
+impl AkomaNtosoParams {
+    fn write_line(&self, writer: &mut impl Write, line: &str) -> Result<()> {
+        for _ in 0..self.indentation_level {
+            writer.write_all(b"  ")?;
+        }
+        writer.write_all(line.as_bytes())?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn indented(&self) -> Self {
+        self.indented_by(1)
+    }
+
+    fn indented_by(&self, levels: usize) -> Self {
+        Self {
+            indentation_level: self.indentation_level + levels,
+            ..self.clone()
+        }
+    }
+
+    fn with_base(&self, base: Reference) -> Self {
+        Self {
+            base,
+            ..self.clone()
+        }
+    }
+
+    fn eid(&self, position: &Reference) -> String {
+        format!("{}{}", self.eid_prefix, position_anchor(position))
+    }
+
+    /// Escaped text, with the outgoing references as `<ref>` elements.
+    /// The base of the parameters must be the position of the element containing the text.
+    fn inline_text(&self, text: &str, semantic_info: &SemanticInfo, eid: &str) -> Result<String> {
+        let mut result = String::new();
+        let mut prev_end = 0;
+        for (i, outgoing_ref) in semantic_info.outgoing_references.iter().enumerate() {
+            let target = outgoing_ref.reference.relative_to(&self.base)?;
+            result.push_str(&escape(&text[prev_end..outgoing_ref.start]));
+            result.push_str(&format!(
+                r#"<ref eId="{eid}__ref_{}" href="{}">{}</ref>"#,
+                i + 1,
+                self.href(&target),
+                escape(&text[outgoing_ref.start..outgoing_ref.end])
+            ));
+            prev_end = outgoing_ref.end;
+        }
+        result.push_str(&escape(&text[prev_end..]));
+        Ok(result)
+    }
+
+    fn href(&self, target: &Reference) -> String {
+        match target.act() {
+            Some(act) if act != self.act => {
+                if target.is_act_only() {
+                    format!("{}/!main", work_uri(act))
+                } else {
+                    format!("{}/!main#{}", work_uri(act), position_anchor(target))
+                }
+            }
+            _ => format!("#{}", position_anchor(target)),
+        }
+    }
+}
+
+/// The FRBR Work URI of an act, e.g. `/akn/hu/act/2013/5` or
+/// `/akn/hu/act/rendelet/korm/2013/123`
+pub fn work_uri(act: ActIdentifier) -> String {
+    match act.kind {
+        DocumentKind::Act => format!("/akn/hu/act/{}/{}", act.year, act.number),
+        DocumentKind::Decree { issuer, .. } | DocumentKind::Resolution { issuer, .. } => format!(
+            "/akn/hu/act/{}/{}/{}/{}",
+            act.kind.name().replace('á', "a"),
+            uri_component(issuer.as_str()),
+            act.year,
+            act.number
+        ),
+    }
+}
+
+/// The eId and the name of the author of the act
+fn author(act: ActIdentifier) -> (String, String) {
+    match act.kind.issuer() {
+        Some(issuer) => (uri_component(issuer.as_str()), issuer.to_string()),
+        None => ("orszaggyules".to_owned(), "Országgyűlés".to_owned()),
+    }
+}
+
+fn uri_component(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::singleton_yaml;
+
+    const TEST_ACT: &str = r#"
+        identifier:
+          year: 2012
+          number: 100
+        subject: A tesztelésről
+        preamble: ""
+        publication_date: 2012-07-13
+        children:
+        - StructuralElement:
+            identifier: "1"
+            title: Általános rendelkezések
+            element_type: Chapter
+        - Subtitle:
+            identifier: "1"
+            title: Fogalmak
+        - Article:
+            identifier: "1"
+            children:
+            - body:
+                intro: "E törvény alkalmazásában"
+                children:
+                  AlphabeticPoint:
+                  - identifier: a
+                    body: "teszt: a 2. § szerinti vizsgálat,"
+                    semantic_info:
+                      outgoing_references:
+                      - start: 9
+                        end: 14
+                        reference:
+                          article: "2"
+                  - identifier: b
+                    body: "próba: a Ptk. 1:3. §-a szerinti <eljárás>."
+        - Subtitle:
+            title: Módosítások
+        - Article:
+            identifier: "2"
+            children:
+            - identifier: "1"
+              body: "Első bekezdés."
+            - identifier: "2"
+              body:
+                intro: "A Ptk. 1:3. § (2) bekezdése helyébe a következő rendelkezés lép:"
+                children:
+                  BlockAmendment:
+                    children:
+                      Paragraph:
+                      - identifier: "2"
+                        body: "Új szöveg."
+              semantic_info:
+                outgoing_references:
+                - start: 2
+                  end: 29
+                  reference:
+                    act:
+                      year: 2013
+                      number: 5
+                    article: "1:3"
+                    paragraph: "2"
+                special_phrase:
+                  BlockAmendment:
+                    position:
+                      act:
+                        year: 2013
+                        number: 5
+                      article: "1:3"
+                      paragraph: "2"
+                    pure_insertion: false
+        - StructuralElement:
+            identifier: "2"
+            title: Záró rendelkezések
+            element_type: Chapter
+        - Subtitle:
+            title: Hatálybalépés
+        - Article:
+            identifier: "3"
+            children:
+            - body: "Ez a törvény a kihirdetését követő napon lép hatályba."
+        signatories:
+        - name: Dr. Teszt Elek
+          role: köztársasági elnök
+        attachments:
+        - identifier: "1"
+          title: A teszt lépései
+          lines:
+          - 1. Előkészítés
+          - 2. Végrehajtás
+          children:
+            NumericPoint:
+            - identifier: "1"
+              body: Előkészítés
+            - identifier: "2"
+              body: Végrehajtás
+        - identifier: "2"
+          lines:
+          - Táblázat
+    "#;
+
+    #[test]
+    fn test_akoma_ntoso_output() {
+        let act: Act = singleton_yaml::from_str(TEST_ACT).unwrap();
+        let mut output = Vec::new();
+        act.write_as_akoma_ntoso(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let expected_fragments = [
+            r#"<FRBRthis value="/akn/hu/act/2012/100/!main"/>"#,
+            r#"<FRBRuri value="/akn/hu/act/2012/100/hun@2012-07-13"/>"#,
+            r#"<chapter eId="chp_1">"#,
+            r#"<subtitle eId="chp_1__subtitle_1">"#,
+            "<subtitle eId=\"chp_1__subtitle_1\">\n          <num>1.</num>\n          <heading>Fogalmak</heading>\n          <article eId=\"art_1\">",
+            r#"<intro><p>E törvény alkalmazásában</p></intro>"#,
+            r##"<p>teszt: a <ref eId="art_1__point_a__ref_1" href="#art_2">2. §</ref> szerinti vizsgálat,</p>"##,
+            r#"<p>próba: a Ptk. 1:3. §-a szerinti &lt;eljárás&gt;.</p>"#,
+            "</subtitle>\n        <subtitle eId=\"chp_1__subtitle_nn1\">",
+            r#"<paragraph eId="art_2__para_1">"#,
+            r#"<p><mod eId="art_2__para_2__mod_1">A <ref eId="art_2__para_2__ref_1" href="/akn/hu/act/2013/5/!main#art_1-3__para_2">Ptk. 1:3. § (2) bekezdése</ref> helyébe a következő rendelkezés lép:"#,
+            r#"<quotedStructure eId="art_2__para_2__mod_1__qstr_1" startQuote="„" endQuote="”">"#,
+            r#"<paragraph eId="art_2__para_2__mod_1__qstr_1__art_1-3__para_2">"#,
+            "</mod></p>",
+            r##"<person refersTo="#signatory_1">Dr. Teszt Elek</person>"##,
+            r#"<hcontainer eId="att_1" name="attachment">"#,
+            "<heading>A teszt lépései</heading>\n        <point eId=\"att_1__point_1\">\n          <num>1.</num>\n          <content>\n            <p>Előkészítés</p>",
+            r#"<point eId="att_1__point_2">"#,
+            "<num>2. melléklet</num>\n        <content>\n          <p>Táblázat</p>\n        </content>\n      </hcontainer>\n    </body>",
+        ];
+        for fragment in expected_fragments {
+            assert!(
+                output.contains(fragment),
+                "{fragment} not found in\n{output}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_eids_match_element_ids() {
+        let act: Act = singleton_yaml::from_str(TEST_ACT).unwrap();
+        let mut output = Vec::new();
+        act.write_as_akoma_ntoso(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        for (id, _) in act.elements().unwrap() {
+            let fragment = format!(r#"eId="{}""#, id.anchor());
+            assert!(
+                output.contains(&fragment),
+                "{fragment} not found in\n{output}"
+            );
+        }
+        // Unnumbered subtitles are counted per structural element
+        assert!(output.contains(r#"<subtitle eId="chp_2__subtitle_nn1">"#));
+    }
+
+    #[test]
+    fn test_work_uri() {
+        assert_eq!(
+            work_uri("123/2013. (IV. 11.) Korm. rendelet".parse().unwrap()),
+            "/akn/hu/act/rendelet/korm/2013/123"
+        );
+        assert_eq!(
+            work_uri("12/2013. (IV. 11.) OGY határozat".parse().unwrap()),
+            "/akn/hu/act/hatarozat/ogy/2013/12"
+        );
+    }
+}
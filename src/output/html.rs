@@ -0,0 +1,866 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Write;
+
+use anyhow::Result;
+
+use super::akoma_ntoso::escape;
+use crate::{
+    element_id::{attachment_anchor, position_anchor, structural_anchor, StructuralPath},
+    identifier::{ActIdentifier, IdentifierCommon},
+    reference::{to_element::ReferenceToElement, Reference},
+    semantic_info::{
+        EnforcementDateType, RepealReference, SemanticInfo, SpecialPhrase, TextAmendmentReference,
+    },
+    structure::{
+        Act, ActChild, AlphabeticPointChildren, AlphabeticSubpointChildren, Article, Attachment,
+        BlockAmendment, BlockAmendmentChildren, ChangeCause, ChildrenCommon, LastChange,
+        NumericPointChildren, NumericSubpointChildren, ParagraphChildren, QuotedBlock, SAEBody,
+        SAEHeaderString, StructuralBlockAmendment, StructuralElement, StructuralElementType,
+        SubArticleElement, Subtitle,
+    },
+    util::compact_string::CompactString,
+};
+
+/// Links to other acts point to the files written by the CLI into the same directory.
+pub const DEFAULT_ACT_URL_PATTERN: &str = "{file}.html";
+
+const STYLE: &str = "
+.act { font-family: serif; max-width: 50em; }
+.structural-element, .subtitle { text-align: center; }
+.sae { margin-left: 1.5em; }
+.article > .sae { margin-left: 0; }
+.header { font-weight: bold; }
+blockquote { border-left: 2px solid #ccc; padding-left: 1em; }
+.annotation { font-family: sans-serif; font-size: small; color: #666; }
+a.reference { color: #06c; }
+";
+
+#[derive(Debug, Clone)]
+pub struct HtmlOutputParams {
+    /// URL of other acts. `{act}` is replaced with the compact identifier of the act,
+    /// `{file}` with its file name stem. The anchor of the element is appended to it.
+    pub act_url_pattern: String,
+    /// The act being written. References to it are written as local links.
+    act: ActIdentifier,
+    /// Absolute position of the parent element
+    base: Reference,
+    /// Prepended to all ids. Used for elements in quoted structures.
+    id_prefix: String,
+}
+
+pub trait HtmlOutput {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()>;
+}
+
+impl Act {
+    /// Write the act as a standalone HTML document. Use `write_as_html` for
+    /// a fragment that can be embedded into other pages.
+    pub fn write_as_html_document(
+        &self,
+        writer: &mut impl Write,
+        params: HtmlOutputParams,
+    ) -> Result<()> {
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, r#"<html lang="hu">"#)?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, r#"<meta charset="utf-8">"#)?;
+        writeln!(
+            writer,
+            "<title>{} {}</title>",
+            escape(&self.identifier.to_string()),
+            escape(&self.subject)
+        )?;
+        writeln!(writer, "<style>{STYLE}</style>")?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        self.write_as_html(writer, params)?;
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+        Ok(())
+    }
+}
+
+impl HtmlOutput for Act {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        let params = HtmlOutputParams {
+            act: self.identifier,
+            base: self.reference(),
+            id_prefix: String::new(),
+            ..params
+        };
+        writeln!(writer, r#"<article class="act">"#)?;
+        writeln!(
+            writer,
+            "<header><h1>{}</h1><p class=\"subject\">{}</p></header>",
+            escape(&self.identifier.to_string()),
+            escape(&self.subject)
+        )?;
+        if !self.preamble.is_empty() {
+            writeln!(
+                writer,
+                r#"<p class="preamble">{}</p>"#,
+                escape(&self.preamble)
+            )?;
+        }
+        self.children
+            .as_slice()
+            .write_as_html(writer, params.clone())?;
+        if !self.signatories.is_empty() {
+            writeln!(writer, r#"<div class="signatories">"#)?;
+            for signatory in &self.signatories {
+                writeln!(
+                    writer,
+                    "<p>{} s. k., {}</p>",
+                    escape(&signatory.name),
+                    escape(&signatory.role)
+                )?;
+            }
+            writeln!(writer, "</div>")?;
+        }
+        for attachment in &self.attachments {
+            attachment.write_as_html(writer, params.clone())?;
+        }
+        if !self.footnotes.is_empty() {
+            writeln!(writer, r#"<div class="footnotes">"#)?;
+            for footnote in &self.footnotes {
+                writeln!(
+                    writer,
+                    r#"<p><span class="marker">{}</span> {}</p>"#,
+                    escape(&footnote.marker),
+                    escape(&footnote.text)
+                )?;
+            }
+            writeln!(writer, "</div>")?;
+        }
+        writeln!(writer, "</article>")?;
+        Ok(())
+    }
+}
+
+/// Structural elements and subtitles are identified by their path in the act,
+/// so they are written by the list of act children.
+impl HtmlOutput for [ActChild] {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        let mut structural_path = StructuralPath::default();
+        for child in self {
+            let id = structural_path
+                .step(child)
+                .map(|path| format!("{}{}", params.id_prefix, structural_anchor(path)))
+                .unwrap_or_default();
+            match child {
+                ActChild::StructuralElement(x) => {
+                    x.write_as_html_with_id(writer, params.clone(), &id)?
+                }
+                ActChild::Subtitle(x) => x.write_as_html_with_id(writer, params.clone(), &id)?,
+                ActChild::Article(x) => x.write_as_html(writer, params.clone())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+trait HtmlOutputWithId {
+    fn write_as_html_with_id(
+        &self,
+        writer: &mut impl Write,
+        params: HtmlOutputParams,
+        id: &str,
+    ) -> Result<()>;
+}
+
+impl HtmlOutputWithId for StructuralElement {
+    fn write_as_html_with_id(
+        &self,
+        writer: &mut impl Write,
+        params: HtmlOutputParams,
+        id: &str,
+    ) -> Result<()> {
+        let class = match self.element_type {
+            StructuralElementType::Book => "book",
+            StructuralElementType::Part { .. } => "part",
+            StructuralElementType::Title => "title",
+            StructuralElementType::Chapter => "chapter",
+        };
+        let mut heading = escape(&self.header_string()?);
+        if !self.title.is_empty() {
+            heading.push_str("<br>");
+            heading.push_str(&escape(&self.title));
+        }
+        writeln!(
+            writer,
+            r#"<h2 class="structural-element {class}" id="{id}">{heading}</h2>"#
+        )?;
+        params.write_last_change(writer, &self.last_change)
+    }
+}
+
+impl HtmlOutputWithId for Subtitle {
+    fn write_as_html_with_id(
+        &self,
+        writer: &mut impl Write,
+        params: HtmlOutputParams,
+        id: &str,
+    ) -> Result<()> {
+        let heading = match self.identifier {
+            Some(identifier) => format!("{}. {}", identifier.with_slash(), self.title),
+            None => self.title.clone(),
+        };
+        writeln!(
+            writer,
+            r#"<h3 class="subtitle" id="{id}">{}</h3>"#,
+            escape(&heading)
+        )?;
+        params.write_last_change(writer, &self.last_change)
+    }
+}
+
+impl HtmlOutput for Article {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        let position = self.reference().relative_to(&params.base)?;
+        writeln!(
+            writer,
+            r#"<section class="article" id="{}">"#,
+            params.id(&position)
+        )?;
+        let mut heading = escape(&self.header_string());
+        if let Some(title) = &self.title {
+            heading.push_str(&format!(
+                r#" <span class="title">[{}]</span>"#,
+                escape(title)
+            ));
+        }
+        writeln!(writer, r#"<h4 class="header">{heading}</h4>"#)?;
+        params.write_last_change(writer, &self.last_change)?;
+        let children_params = params.with_base(position);
+        for child in &self.children {
+            child.write_as_html(writer, children_params.clone())?;
+        }
+        writeln!(writer, "</section>")?;
+        Ok(())
+    }
+}
+
+impl HtmlOutput for Attachment {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        writeln!(
+            writer,
+            r#"<section class="attachment" id="{}">"#,
+            attachment_anchor(self.identifier)
+        )?;
+        writeln!(writer, "<h2>{}</h2>", escape(&self.header_string()))?;
+        if !self.title.is_empty() {
+            writeln!(writer, "<h3>{}</h3>", escape(&self.title))?;
+        }
+        params.write_last_change(writer, &self.last_change)?;
+        if let Some(children) = &self.children {
+            // Attachment contents have no article, so their positions are not
+            // relative to the act.
+            let mut children_params = params.with_base(Reference::default());
+            children_params.id_prefix = format!("{}__", attachment_anchor(self.identifier));
+            children.write_as_html(writer, children_params)?;
+        } else {
+            // Tables and such are kept as they are
+            for line in &self.lines {
+                writeln!(writer, "<p>{}</p>", escape(line))?;
+            }
+        }
+        writeln!(writer, "</section>")?;
+        Ok(())
+    }
+}
+
+impl<IT, CT> HtmlOutput for SubArticleElement<IT, CT>
+where
+    SubArticleElement<IT, CT>: SAEHeaderString + ReferenceToElement,
+    IT: IdentifierCommon,
+    CT: ChildrenCommon + HtmlOutput,
+{
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        let position = self.reference().relative_to(&params.base)?;
+        let id = params.id(&position);
+        // The only paragraph of an article has no identifier, and it has the same
+        // position as the article, so it does not get its own element.
+        let has_own_element = !self.identifier.is_empty();
+        let header = if has_own_element {
+            writeln!(
+                writer,
+                r#"<div class="sae {}" id="{id}">"#,
+                kebab_case(CT::parent_type_name())
+            )?;
+            format!(
+                r#"<span class="header">{}</span> "#,
+                escape(&self.header_string())
+            )
+        } else {
+            String::new()
+        };
+        let text_params = params.with_base(position.clone());
+        match &self.body {
+            SAEBody::Text(text) => {
+                writeln!(
+                    writer,
+                    "<p>{header}{}</p>",
+                    text_params.linked_text(text, &self.semantic_info)?
+                )?;
+                text_params.write_annotations(writer, &self.semantic_info)?;
+            }
+            SAEBody::Children {
+                intro,
+                children,
+                wrap_up,
+            } => {
+                writeln!(
+                    writer,
+                    "<p>{header}{}</p>",
+                    text_params.linked_text(intro, &self.semantic_info)?
+                )?;
+                text_params.write_annotations(writer, &self.semantic_info)?;
+                // Quoted structures are relative to the amended element
+                let mut children_params = text_params.clone();
+                match &self.semantic_info.special_phrase {
+                    Some(SpecialPhrase::BlockAmendment(ba)) => {
+                        children_params.id_prefix = format!("{id}__qstr_1__");
+                        children_params.base = ba.position.relative_to(&position)?.parent();
+                    }
+                    Some(SpecialPhrase::StructuralBlockAmendment(sba)) => {
+                        children_params.id_prefix = format!("{id}__qstr_1__");
+                        children_params.base = sba.position.act.unwrap_or(params.act).into();
+                    }
+                    _ => (),
+                }
+                children.write_as_html(writer, children_params)?;
+                if let Some(wrap_up) = wrap_up {
+                    writeln!(writer, "<p>{}</p>", escape(wrap_up))?;
+                }
+            }
+        }
+        params.write_last_change(writer, &self.last_change)?;
+        if has_own_element {
+            writeln!(writer, "</div>")?;
+        }
+        Ok(())
+    }
+}
+
+impl HtmlOutput for ParagraphChildren {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        match self {
+            ParagraphChildren::AlphabeticPoint(x) => x.write_as_html(writer, params),
+            ParagraphChildren::NumericPoint(x) => x.write_as_html(writer, params),
+            ParagraphChildren::QuotedBlock(x) => x.write_as_html(writer, params),
+            ParagraphChildren::BlockAmendment(x) => x.write_as_html(writer, params),
+            ParagraphChildren::StructuralBlockAmendment(x) => x.write_as_html(writer, params),
+        }
+    }
+}
+
+impl HtmlOutput for AlphabeticPointChildren {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        match self {
+            AlphabeticPointChildren::AlphabeticSubpoint(x) => x.write_as_html(writer, params),
+            AlphabeticPointChildren::NumericSubpoint(x) => x.write_as_html(writer, params),
+        }
+    }
+}
+
+impl HtmlOutput for NumericPointChildren {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        match self {
+            NumericPointChildren::AlphabeticSubpoint(x) => x.write_as_html(writer, params),
+        }
+    }
+}
+
+impl HtmlOutput for AlphabeticSubpointChildren {
+    fn write_as_html(&self, _writer: &mut impl Write, _params: HtmlOutputParams) -> Result<()> {
+        // This is an empty enum, the function shall never run.
+        match *self {}
+    }
+}
+
+impl HtmlOutput for NumericSubpointChildren {
+    fn write_as_html(&self, _writer: &mut impl Write, _params: HtmlOutputParams) -> Result<()> {
+        // This is an empty enum, the function shall never run.
+        match *self {}
+    }
+}
+
+impl HtmlOutput for QuotedBlock {
+    fn write_as_html(&self, writer: &mut impl Write, _params: HtmlOutputParams) -> Result<()> {
+        writeln!(writer, r#"<blockquote class="quoted-block">"#)?;
+        if let Some(intro) = &self.intro {
+            writeln!(writer, "<p>{}</p>", escape(intro))?;
+        }
+        let lines: Vec<String> = self
+            .lines
+            .iter()
+            .map(|line| escape(line.content()))
+            .collect();
+        writeln!(writer, "<p>{}</p>", lines.join("<br>"))?;
+        if let Some(wrap_up) = &self.wrap_up {
+            writeln!(writer, "<p>{}</p>", escape(wrap_up))?;
+        }
+        writeln!(writer, "</blockquote>")?;
+        Ok(())
+    }
+}
+
+impl HtmlOutput for BlockAmendment {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        writeln!(writer, r#"<blockquote class="block-amendment">"#)?;
+        if let Some(intro) = &self.intro {
+            writeln!(writer, "<p>{}</p>", escape(intro))?;
+        }
+        self.children.write_as_html(writer, params)?;
+        if let Some(wrap_up) = &self.wrap_up {
+            writeln!(writer, "<p>{}</p>", escape(wrap_up))?;
+        }
+        writeln!(writer, "</blockquote>")?;
+        Ok(())
+    }
+}
+
+impl HtmlOutput for StructuralBlockAmendment {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        writeln!(writer, r#"<blockquote class="block-amendment">"#)?;
+        if let Some(intro) = &self.intro {
+            writeln!(writer, "<p>{}</p>", escape(intro))?;
+        }
+        self.children.as_slice().write_as_html(writer, params)?;
+        if let Some(wrap_up) = &self.wrap_up {
+            writeln!(writer, "<p>{}</p>", escape(wrap_up))?;
+        }
+        writeln!(writer, "</blockquote>")?;
+        Ok(())
+    }
+}
+
+impl HtmlOutput for BlockAmendmentChildren {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        match self {
+            BlockAmendmentChildren::Paragraph(x) => x.write_as_html(writer, params),
+            BlockAmendmentChildren::AlphabeticPoint(x) => x.write_as_html(writer, params),
+            BlockAmendmentChildren::NumericPoint(x) => x.write_as_html(writer, params),
+            BlockAmendmentChildren::AlphabeticSubpoint(x) => x.write_as_html(writer, params),
+            BlockAmendmentChildren::NumericSubpoint(x) => x.write_as_html(writer, params),
+        }
+    }
+}
+
+impl<T: HtmlOutput> HtmlOutput for Vec<T> {
+    fn write_as_html(&self, writer: &mut impl Write, params: HtmlOutputParams) -> Result<()> {
+        for element in self {
+            element.write_as_html(writer, params.clone())?;
+        }
+        Ok(())
+    }
+}
+
+impl HtmlOutputParams {
+    pub fn new(act_url_pattern: impl Into<String>) -> Self {
+        Self {
+            act_url_pattern: act_url_pattern.into(),
+            act: Default::default(),
+            base: Default::default(),
+            id_prefix: String::new(),
+        }
+    }
+
+    fn with_base(&self, base: Reference) -> Self {
+        Self {
+            base,
+            ..self.clone()
+        }
+    }
+
+    fn id(&self, position: &Reference) -> String {
+        format!("{}{}", self.id_prefix, position_anchor(position))
+    }
+
+    /// Escaped text, with the outgoing references as links.
+    /// The base of the parameters must be the position of the element containing the text.
+    fn linked_text(&self, text: &str, semantic_info: &SemanticInfo) -> Result<String> {
+        let mut result = String::new();
+        let mut prev_end = 0;
+        for outgoing_ref in &semantic_info.outgoing_references {
+            result.push_str(&escape(&text[prev_end..outgoing_ref.start]));
+            result.push_str(&self.link(
+                &outgoing_ref.reference.relative_to(&self.base)?,
+                &text[outgoing_ref.start..outgoing_ref.end],
+            ));
+            prev_end = outgoing_ref.end;
+        }
+        result.push_str(&escape(&text[prev_end..]));
+        Ok(result)
+    }
+
+    /// Link to an absolute reference
+    fn link(&self, target: &Reference, text: &str) -> String {
+        format!(
+            r#"<a class="reference" href="{}">{}</a>"#,
+            escape(&self.href(target)),
+            escape(text)
+        )
+    }
+
+    fn href(&self, target: &Reference) -> String {
+        href(&self.act_url_pattern, self.act, target)
+    }
+
+    /// The special phrase of the element, as a human readable annotation
+    fn write_annotations(
+        &self,
+        writer: &mut impl Write,
+        semantic_info: &SemanticInfo,
+    ) -> Result<()> {
+        let annotation = match &semantic_info.special_phrase {
+            None => return Ok(()),
+            Some(SpecialPhrase::BlockAmendment(ba)) => {
+                let target = ba.position.relative_to(&self.base)?;
+                let verb = if ba.pure_insertion {
+                    "Inserts"
+                } else {
+                    "Amends"
+                };
+                format!("{verb} {}", self.link(&target, &target.to_string()))
+            }
+            Some(SpecialPhrase::StructuralBlockAmendment(sba)) => {
+                let verb = if sba.pure_insertion {
+                    "Inserts"
+                } else {
+                    "Amends"
+                };
+                let act = sba.position.act.unwrap_or(self.act);
+                format!(
+                    "{verb} structural elements in {}",
+                    self.link(&act.into(), &act.to_string())
+                )
+            }
+            Some(SpecialPhrase::TextAmendment(text_amendments)) => {
+                let mut parts = Vec::new();
+                for text_amendment in text_amendments {
+                    let target = match &text_amendment.reference {
+                        TextAmendmentReference::SAE { reference, .. }
+                        | TextAmendmentReference::ArticleTitle(reference) => {
+                            let reference = reference.relative_to(&self.base)?;
+                            self.link(&reference, &reference.to_string())
+                        }
+                        TextAmendmentReference::Structural(_) => "a structural element".to_owned(),
+                    };
+                    parts.push(format!(
+                        "replaces „{}” with „{}” in {target}",
+                        escape(&text_amendment.from),
+                        escape(&text_amendment.to)
+                    ));
+                }
+                format!("Text amendment: {}", parts.join("; "))
+            }
+            Some(SpecialPhrase::Repeal(repeals)) => {
+                let mut targets = Vec::new();
+                for repeal in repeals {
+                    targets.push(match repeal {
+                        RepealReference::Reference(reference) => {
+                            let reference = reference.relative_to(&self.base)?;
+                            self.link(&reference, &reference.to_string())
+                        }
+                        RepealReference::StructuralReference(_) => {
+                            "a structural element".to_owned()
+                        }
+                    });
+                }
+                format!("Repeals {}", targets.join(", "))
+            }
+            Some(SpecialPhrase::EnforcementDate(ed)) => {
+                let mut result = if ed.is_default {
+                    "Enforcement date of the act".to_owned()
+                } else {
+                    let mut targets = Vec::new();
+                    for position in &ed.positions {
+                        let position = position.relative_to(&self.base)?;
+                        targets.push(self.link(&position, &position.to_string()));
+                    }
+                    if !ed.structural_positions.is_empty() {
+                        targets.push("structural elements".to_owned());
+                    }
+                    if !ed.attachment_positions.is_empty() {
+                        targets.push("attachments".to_owned());
+                    }
+                    format!("Enforcement date of {}", targets.join(", "))
+                };
+                result.push_str(": ");
+                result.push_str(&match ed.date {
+                    EnforcementDateType::Date(date) => date.to_string(),
+                    EnforcementDateType::DaysAfterPublication(1) => {
+                        "the day after publication".to_owned()
+                    }
+                    EnforcementDateType::DaysAfterPublication(days) => {
+                        format!("{days} days after publication")
+                    }
+                    EnforcementDateType::DayInMonthAfterPublication { month, day } => {
+                        format!(
+                            "day {day} of month {} after publication",
+                            month.unwrap_or(1)
+                        )
+                    }
+                });
+                if let Some(inline_repeal) = ed.inline_repeal {
+                    result.push_str(&format!(", repealed on {inline_repeal}"));
+                }
+                result
+            }
+        };
+        writeln!(
+            writer,
+            r#"<aside class="annotation special-phrase">{annotation}</aside>"#
+        )?;
+        Ok(())
+    }
+
+    fn write_last_change(
+        &self,
+        writer: &mut impl Write,
+        last_change: &Option<LastChange>,
+    ) -> Result<()> {
+        if let Some(last_change) = last_change {
+            let cause = match &last_change.cause {
+                ChangeCause::Amendment(reference) => {
+                    format!(
+                        "amended by {}",
+                        self.link(reference, &reference.to_string())
+                    )
+                }
+                ChangeCause::AutoRepeal => "automatically repealed".to_owned(),
+                ChangeCause::InlineRepeal => "repealed by the act itself".to_owned(),
+                ChangeCause::Other(cause) => escape(cause),
+            };
+            writeln!(
+                writer,
+                r#"<aside class="annotation last-change">Last changed on {}: {cause}</aside>"#,
+                last_change.date
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for HtmlOutputParams {
+    fn default() -> Self {
+        Self::new(DEFAULT_ACT_URL_PATTERN)
+    }
+}
+
+/// Link to an absolute reference from the document of `act`. Elements of other acts
+/// are linked using `act_url_pattern` (see `HtmlOutputParams::act_url_pattern`).
+pub(super) fn href(act_url_pattern: &str, act: ActIdentifier, target: &Reference) -> String {
+    let anchor = if target.is_act_only() {
+        String::new()
+    } else {
+        format!("#{}", position_anchor(target))
+    };
+    match target.act() {
+        Some(target_act) if target_act != act => {
+            let url = act_url_pattern
+                .replace("{act}", &target_act.compact_string().to_string())
+                .replace("{file}", &url_encode(&target_act.file_name_stem()));
+            format!("{url}{anchor}")
+        }
+        _ if anchor.is_empty() => "#".to_owned(),
+        _ => anchor,
+    }
+}
+
+/// "AlphabeticPoint" -> "alphabetic-point"
+fn kebab_case(s: &str) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        if c.is_uppercase() && !result.is_empty() {
+            result.push('-');
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+/// Only encodes the characters that have a special meaning in URLs, or are
+/// not allowed in them. The rest of the UTF-8 characters are left as is.
+fn url_encode(s: &str) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        match c {
+            ' ' | '"' | '#' | '%' | '&' | '?' | '<' | '>' => {
+                result.push_str(&format!("%{:02X}", c as u32))
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::singleton_yaml;
+
+    const TEST_ACT: &str = r#"
+        identifier:
+          year: 2012
+          number: 100
+        subject: A tesztelésről
+        preamble: ""
+        publication_date: 2012-07-13
+        children:
+        - StructuralElement:
+            identifier: "1"
+            title: Általános rendelkezések
+            element_type: Chapter
+        - Subtitle:
+            title: Fogalmak
+        - Article:
+            identifier: "1"
+            children:
+            - body:
+                intro: "E törvény alkalmazásában"
+                children:
+                  AlphabeticPoint:
+                  - identifier: a
+                    body: "teszt: a 2. § szerinti <vizsgálat>,"
+                    semantic_info:
+                      outgoing_references:
+                      - start: 9
+                        end: 14
+                        reference:
+                          article: "2"
+                  - identifier: b
+                    body: "próba: ellenőrzés."
+                    last_change:
+                      date: 2013-01-01
+                      cause:
+                        Amendment:
+                          act:
+                            year: 2012
+                            number: 200
+                          article: "5"
+        - Article:
+            identifier: "2"
+            children:
+            - identifier: "1"
+              body: "A Ptk. 1:3. § (2) bekezdése helyébe a következő rendelkezés lép:"
+              semantic_info:
+                outgoing_references:
+                - start: 2
+                  end: 29
+                  reference:
+                    act:
+                      year: 2013
+                      number: 5
+                    article: "1:3"
+                    paragraph: "2"
+                special_phrase:
+                  BlockAmendment:
+                    position:
+                      act:
+                        year: 2013
+                        number: 5
+                      article: "1:3"
+                      paragraph: "2"
+                    pure_insertion: false
+            - identifier: "2"
+              body: "Ez a törvény a kihirdetését követő napon lép hatályba."
+              semantic_info:
+                special_phrase:
+                  EnforcementDate:
+                    is_default: true
+                    date:
+                      DaysAfterPublication: 1
+        attachments:
+        - identifier: "1"
+          title: A teszt lépései
+          lines:
+          - 1. Előkészítés
+          - 2. Végrehajtás
+          children:
+            NumericPoint:
+            - identifier: "1"
+              body: Előkészítés
+            - identifier: "2"
+              body: Végrehajtás
+        - identifier: "2"
+          lines:
+          - Táblázat
+    "#;
+
+    #[test]
+    fn test_html_output() {
+        let act: Act = singleton_yaml::from_str(TEST_ACT).unwrap();
+        let mut output = Vec::new();
+        act.write_as_html_document(&mut output, HtmlOutputParams::new("/wiki/{act}"))
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let expected_fragments = [
+            "<title>2012. évi C. törvény A tesztelésről</title>",
+            r#"<h2 class="structural-element chapter" id="chp_1">I. FEJEZET<br>Általános rendelkezések</h2>"#,
+            r#"<h3 class="subtitle" id="chp_1__subtitle_nn1">Fogalmak</h3>"#,
+            r#"<section class="article" id="art_1">"#,
+            "<h4 class=\"header\">1. §</h4>\n<p>E törvény alkalmazásában</p>",
+            r#"<div class="sae alphabetic-point" id="art_1__point_a">"#,
+            r##"<p><span class="header">a)</span> teszt: a <a class="reference" href="#art_2">2. §</a> szerinti &lt;vizsgálat&gt;,</p>"##,
+            r#"<aside class="annotation last-change">Last changed on 2013-01-01: amended by <a class="reference" href="/wiki/2012.200#art_5">"#,
+            r#"<div class="sae paragraph" id="art_2__para_1">"#,
+            r#"<a class="reference" href="/wiki/2013.5#art_1-3__para_2">Ptk. 1:3. § (2) bekezdése</a> helyébe"#,
+            r#"<aside class="annotation special-phrase">Amends <a class="reference" href="/wiki/2013.5#art_1-3__para_2">"#,
+            r#"<aside class="annotation special-phrase">Enforcement date of the act: the day after publication</aside>"#,
+            r#"<section class="attachment" id="att_1">"#,
+            "<h3>A teszt lépései</h3>\n<div class=\"sae numeric-point\" id=\"att_1__point_1\">\n<p><span class=\"header\">1.</span> Előkészítés</p>",
+            r#"<div class="sae numeric-point" id="att_1__point_2">"#,
+            "<h2>2. melléklet</h2>\n<p>Táblázat</p>\n</section>",
+        ];
+        for fragment in expected_fragments {
+            assert!(
+                output.contains(fragment),
+                "{fragment} not found in\n{output}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ids_match_element_ids() {
+        let act: Act = singleton_yaml::from_str(TEST_ACT).unwrap();
+        let mut output = Vec::new();
+        act.write_as_html_document(&mut output, HtmlOutputParams::default())
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        for (id, _) in act.elements().unwrap() {
+            let fragment = format!(r#"id="{}""#, id.anchor());
+            assert!(
+                output.contains(&fragment),
+                "{fragment} not found in\n{output}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_url_pattern() {
+        let params = HtmlOutputParams::default();
+        let reference = Reference::from(ActIdentifier {
+            year: 2013,
+            number: 5,
+            ..Default::default()
+        });
+        assert_eq!(params.href(&reference), "2013.%20évi%20V.%20törvény.html");
+    }
+}
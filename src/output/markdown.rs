@@ -0,0 +1,785 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Write;
+
+use anyhow::Result;
+
+use super::html::href;
+use crate::{
+    element_id::{attachment_anchor, position_anchor, structural_anchor, StructuralPath},
+    identifier::{ActIdentifier, IdentifierCommon},
+    reference::{to_element::ReferenceToElement, Reference},
+    semantic_info::{SemanticInfo, SpecialPhrase},
+    structure::{
+        Act, ActChild, AlphabeticPointChildren, AlphabeticSubpointChildren, Article, Attachment,
+        BlockAmendment, BlockAmendmentChildren, ChildrenCommon, NumericPointChildren,
+        NumericSubpointChildren, ParagraphChildren, QuotedBlock, SAEBody, SAEHeaderString,
+        StructuralBlockAmendment, StructuralElement, SubArticleElement, Subtitle,
+    },
+};
+
+/// Links to other acts point to the files written by the CLI into the same directory.
+pub const DEFAULT_ACT_URL_PATTERN: &str = "{file}.md";
+
+#[derive(Debug, Clone)]
+pub struct MarkdownOutputParams {
+    /// URL of other acts. `{act}` is replaced with the compact identifier of the act,
+    /// `{file}` with its file name stem. The anchor of the element is appended to it.
+    pub act_url_pattern: String,
+    /// The act being written. References to it are written as local links.
+    act: ActIdentifier,
+    /// Absolute position of the parent element
+    base: Reference,
+    /// Prepended to all anchor ids. Used for elements in quoted structures.
+    id_prefix: String,
+    /// Written before every line: list indentation and blockquote markers
+    line_prefix: String,
+}
+
+pub trait MarkdownOutput {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()>;
+}
+
+impl MarkdownOutput for Act {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        let params = MarkdownOutputParams {
+            act: self.identifier,
+            base: self.reference(),
+            id_prefix: String::new(),
+            line_prefix: String::new(),
+            ..params
+        };
+        params.write_line(
+            writer,
+            &format!("# {}", escape(&self.identifier.to_string())),
+        )?;
+        params.write_empty_line(writer)?;
+        params.write_line(writer, &format!("**{}**", escape(&self.subject)))?;
+        if !self.preamble.is_empty() {
+            params.write_empty_line(writer)?;
+            params.write_line(writer, &escape_block_start(&escape(&self.preamble)))?;
+        }
+        self.children
+            .as_slice()
+            .write_as_markdown(writer, params.clone())?;
+        for signatory in &self.signatories {
+            params.write_empty_line(writer)?;
+            params.write_line(
+                writer,
+                &format!(
+                    "*{} s. k.*, {}",
+                    escape(&signatory.name),
+                    escape(&signatory.role)
+                ),
+            )?;
+        }
+        for attachment in &self.attachments {
+            attachment.write_as_markdown(writer, params.clone())?;
+        }
+        if !self.footnotes.is_empty() {
+            params.write_empty_line(writer)?;
+            params.write_line(writer, "---")?;
+        }
+        for footnote in &self.footnotes {
+            params.write_empty_line(writer)?;
+            params.write_line(
+                writer,
+                &format!("{} {}", escape(&footnote.marker), escape(&footnote.text)),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Structural elements and subtitles are identified by their path in the act,
+/// so they are written by the list of act children.
+impl MarkdownOutput for [ActChild] {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        let mut structural_path = StructuralPath::default();
+        for child in self {
+            let id = structural_path
+                .step(child)
+                .map(|path| format!("{}{}", params.id_prefix, structural_anchor(path)))
+                .unwrap_or_default();
+            match child {
+                ActChild::StructuralElement(x) => {
+                    x.write_as_markdown_with_id(writer, params.clone(), &id)?
+                }
+                ActChild::Subtitle(x) => {
+                    x.write_as_markdown_with_id(writer, params.clone(), &id)?
+                }
+                ActChild::Article(x) => x.write_as_markdown(writer, params.clone())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+trait MarkdownOutputWithId {
+    fn write_as_markdown_with_id(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+        id: &str,
+    ) -> Result<()>;
+}
+
+impl MarkdownOutputWithId for StructuralElement {
+    fn write_as_markdown_with_id(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+        id: &str,
+    ) -> Result<()> {
+        let mut heading = self.header_string()?;
+        if !self.title.is_empty() {
+            heading = format!("{heading} – {}", self.title);
+        }
+        params.write_empty_line(writer)?;
+        params.write_line(
+            writer,
+            &format!(r#"## <a id="{id}"></a>{}"#, escape(&heading)),
+        )
+    }
+}
+
+impl MarkdownOutputWithId for Subtitle {
+    fn write_as_markdown_with_id(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+        id: &str,
+    ) -> Result<()> {
+        let heading = match self.identifier {
+            Some(identifier) => format!("{}. {}", identifier.with_slash(), self.title),
+            None => self.title.clone(),
+        };
+        params.write_empty_line(writer)?;
+        params.write_line(
+            writer,
+            &format!(r#"### <a id="{id}"></a>{}"#, escape(&heading)),
+        )
+    }
+}
+
+impl MarkdownOutput for Article {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        let position = self.reference().relative_to(&params.base)?;
+        let mut heading = self.header_string();
+        if let Some(title) = &self.title {
+            heading = format!("{heading} [{title}]");
+        }
+        params.write_empty_line(writer)?;
+        params.write_line(
+            writer,
+            &format!("#### {}{}", params.anchor(&position), escape(&heading)),
+        )?;
+        self.children
+            .write_as_markdown(writer, params.with_base(position))
+    }
+}
+
+impl MarkdownOutput for Attachment {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        let mut heading = self.header_string();
+        if !self.title.is_empty() {
+            heading = format!("{heading} – {}", self.title);
+        }
+        params.write_empty_line(writer)?;
+        params.write_line(
+            writer,
+            &format!(
+                r#"## <a id="{}"></a>{}"#,
+                attachment_anchor(self.identifier),
+                escape(&heading)
+            ),
+        )?;
+        if let Some(children) = &self.children {
+            // Attachment contents have no article, so their positions are not
+            // relative to the act.
+            let mut children_params = params.with_base(Reference::default());
+            children_params.id_prefix = format!("{}__", attachment_anchor(self.identifier));
+            params.write_empty_line(writer)?;
+            children.write_as_markdown(writer, children_params)?;
+        } else {
+            // Tables and such are kept as they are
+            params.write_empty_line(writer)?;
+            params.write_line(writer, "```")?;
+            for line in &self.lines {
+                params.write_line(writer, line)?;
+            }
+            params.write_line(writer, "```")?;
+        }
+        Ok(())
+    }
+}
+
+impl<IT, CT> MarkdownOutput for SubArticleElement<IT, CT>
+where
+    SubArticleElement<IT, CT>: SAEHeaderString + ReferenceToElement,
+    IT: IdentifierCommon,
+    CT: ChildrenCommon + MarkdownChildren,
+{
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        let position = self.reference().relative_to(&params.base)?;
+        // Paragraphs are Markdown paragraphs, everything below them are list items.
+        let is_list_item = CT::PARENT_IS_LIST_ITEM;
+        // The only paragraph of an article has no identifier, and it has the same
+        // position as the article, so it does not get its own anchor.
+        let mut first_line = if self.identifier.is_empty() {
+            String::new()
+        } else {
+            format!("{}{} ", params.anchor(&position), self.header_string())
+        };
+        let text_params = params.with_base(position.clone());
+        let (text, children) = match &self.body {
+            SAEBody::Text(text) => (text, None),
+            SAEBody::Children {
+                intro,
+                children,
+                wrap_up,
+            } => (intro, Some((children, wrap_up))),
+        };
+        first_line.push_str(&text_params.linked_text(text, &self.semantic_info)?);
+        let first_line = escape_block_start(&first_line);
+        let content_params = if is_list_item {
+            params.write_line(writer, &format!("- {first_line}"))?;
+            text_params.nested("  ")
+        } else {
+            params.write_empty_line(writer)?;
+            params.write_line(writer, &first_line)?;
+            text_params
+        };
+        if let Some((children, wrap_up)) = children {
+            if !is_list_item {
+                params.write_empty_line(writer)?;
+            }
+            // Quoted structures are relative to the amended element
+            let mut children_params = content_params.clone();
+            match &self.semantic_info.special_phrase {
+                Some(SpecialPhrase::BlockAmendment(ba)) => {
+                    children_params.id_prefix = format!("{}__qstr_1__", params.id(&position));
+                    children_params.base = ba.position.relative_to(&position)?.parent();
+                }
+                Some(SpecialPhrase::StructuralBlockAmendment(sba)) => {
+                    children_params.id_prefix = format!("{}__qstr_1__", params.id(&position));
+                    children_params.base = sba.position.act.unwrap_or(params.act).into();
+                }
+                _ => (),
+            }
+            children.write_as_markdown(writer, children_params)?;
+            if let Some(wrap_up) = wrap_up {
+                content_params.write_empty_line(writer)?;
+                content_params.write_line(writer, &escape_block_start(&escape(wrap_up)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Children of SAEs
+pub trait MarkdownChildren: MarkdownOutput {
+    /// Whether the SAE containing these children is written as a list item
+    const PARENT_IS_LIST_ITEM: bool;
+}
+
+impl MarkdownOutput for ParagraphChildren {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        match self {
+            ParagraphChildren::AlphabeticPoint(x) => x.write_as_markdown(writer, params),
+            ParagraphChildren::NumericPoint(x) => x.write_as_markdown(writer, params),
+            ParagraphChildren::QuotedBlock(x) => x.write_as_markdown(writer, params),
+            ParagraphChildren::BlockAmendment(x) => x.write_as_markdown(writer, params),
+            ParagraphChildren::StructuralBlockAmendment(x) => x.write_as_markdown(writer, params),
+        }
+    }
+}
+
+impl MarkdownChildren for ParagraphChildren {
+    const PARENT_IS_LIST_ITEM: bool = false;
+}
+
+impl MarkdownOutput for AlphabeticPointChildren {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        match self {
+            AlphabeticPointChildren::AlphabeticSubpoint(x) => x.write_as_markdown(writer, params),
+            AlphabeticPointChildren::NumericSubpoint(x) => x.write_as_markdown(writer, params),
+        }
+    }
+}
+
+impl MarkdownChildren for AlphabeticPointChildren {
+    const PARENT_IS_LIST_ITEM: bool = true;
+}
+
+impl MarkdownOutput for NumericPointChildren {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        match self {
+            NumericPointChildren::AlphabeticSubpoint(x) => x.write_as_markdown(writer, params),
+        }
+    }
+}
+
+impl MarkdownChildren for NumericPointChildren {
+    const PARENT_IS_LIST_ITEM: bool = true;
+}
+
+impl MarkdownOutput for AlphabeticSubpointChildren {
+    fn write_as_markdown(
+        &self,
+        _writer: &mut impl Write,
+        _params: MarkdownOutputParams,
+    ) -> Result<()> {
+        // This is an empty enum, the function shall never run.
+        match *self {}
+    }
+}
+
+impl MarkdownChildren for AlphabeticSubpointChildren {
+    const PARENT_IS_LIST_ITEM: bool = true;
+}
+
+impl MarkdownOutput for NumericSubpointChildren {
+    fn write_as_markdown(
+        &self,
+        _writer: &mut impl Write,
+        _params: MarkdownOutputParams,
+    ) -> Result<()> {
+        // This is an empty enum, the function shall never run.
+        match *self {}
+    }
+}
+
+impl MarkdownChildren for NumericSubpointChildren {
+    const PARENT_IS_LIST_ITEM: bool = true;
+}
+
+impl MarkdownOutput for QuotedBlock {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        if let Some(intro) = &self.intro {
+            params.write_line(writer, &format!("*({})*", escape(intro)))?;
+        }
+        let quote_params = params.nested("> ");
+        let lines: Vec<String> = self
+            .lines
+            .iter()
+            .map(|line| escape_block_start(&escape(line.content())))
+            .collect();
+        // Hard line breaks, so that the lines are kept as they are
+        quote_params.write_line(writer, &lines.join("\\\n"))?;
+        params.write_empty_line(writer)?;
+        if let Some(wrap_up) = &self.wrap_up {
+            params.write_line(writer, &format!("*({})*", escape(wrap_up)))?;
+        }
+        Ok(())
+    }
+}
+
+impl MarkdownOutput for BlockAmendment {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        write_quoted_structure(
+            writer,
+            params,
+            self.intro.as_deref(),
+            self.wrap_up.as_deref(),
+            |writer, params| self.children.write_as_markdown(writer, params),
+        )
+    }
+}
+
+impl MarkdownOutput for StructuralBlockAmendment {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        write_quoted_structure(
+            writer,
+            params,
+            self.intro.as_deref(),
+            self.wrap_up.as_deref(),
+            |writer, params| self.children.as_slice().write_as_markdown(writer, params),
+        )
+    }
+}
+
+fn write_quoted_structure<W: Write>(
+    writer: &mut W,
+    params: MarkdownOutputParams,
+    intro: Option<&str>,
+    wrap_up: Option<&str>,
+    write_children: impl FnOnce(&mut W, MarkdownOutputParams) -> Result<()>,
+) -> Result<()> {
+    if let Some(intro) = intro {
+        params.write_line(writer, &format!("*({})*", escape(intro)))?;
+    }
+    write_children(writer, params.nested("> "))?;
+    params.write_empty_line(writer)?;
+    if let Some(wrap_up) = wrap_up {
+        params.write_line(writer, &format!("*({})*", escape(wrap_up)))?;
+    }
+    Ok(())
+}
+
+impl MarkdownOutput for BlockAmendmentChildren {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        match self {
+            BlockAmendmentChildren::Paragraph(x) => x.write_as_markdown(writer, params),
+            BlockAmendmentChildren::AlphabeticPoint(x) => x.write_as_markdown(writer, params),
+            BlockAmendmentChildren::NumericPoint(x) => x.write_as_markdown(writer, params),
+            BlockAmendmentChildren::AlphabeticSubpoint(x) => x.write_as_markdown(writer, params),
+            BlockAmendmentChildren::NumericSubpoint(x) => x.write_as_markdown(writer, params),
+        }
+    }
+}
+
+impl<T: MarkdownOutput> MarkdownOutput for Vec<T> {
+    fn write_as_markdown(
+        &self,
+        writer: &mut impl Write,
+        params: MarkdownOutputParams,
+    ) -> Result<()> {
+        for element in self {
+            element.write_as_markdown(writer, params.clone())?;
+        }
+        Ok(())
+    }
+}
+
+impl MarkdownOutputParams {
+    pub fn new(act_url_pattern: impl Into<String>) -> Self {
+        Self {
+            act_url_pattern: act_url_pattern.into(),
+            act: Default::default(),
+            base: Default::default(),
+            id_prefix: String::new(),
+            line_prefix: String::new(),
+        }
+    }
+
+    fn write_line(&self, writer: &mut impl Write, text: &str) -> Result<()> {
+        for line in text.split('\n') {
+            writeln!(writer, "{}{line}", self.line_prefix)?;
+        }
+        Ok(())
+    }
+
+    fn write_empty_line(&self, writer: &mut impl Write) -> Result<()> {
+        writeln!(writer, "{}", self.line_prefix.trim_end())?;
+        Ok(())
+    }
+
+    fn nested(&self, prefix: &str) -> Self {
+        Self {
+            line_prefix: format!("{}{prefix}", self.line_prefix),
+            ..self.clone()
+        }
+    }
+
+    fn with_base(&self, base: Reference) -> Self {
+        Self {
+            base,
+            ..self.clone()
+        }
+    }
+
+    fn id(&self, position: &Reference) -> String {
+        format!("{}{}", self.id_prefix, position_anchor(position))
+    }
+
+    /// Inline HTML anchor, because Markdown has no standard way to add ids to elements
+    fn anchor(&self, position: &Reference) -> String {
+        format!(r#"<a id="{}"></a>"#, self.id(position))
+    }
+
+    /// Escaped text, with the outgoing references as links.
+    /// The base of the parameters must be the position of the element containing the text.
+    fn linked_text(&self, text: &str, semantic_info: &SemanticInfo) -> Result<String> {
+        let mut result = String::new();
+        let mut prev_end = 0;
+        for outgoing_ref in &semantic_info.outgoing_references {
+            let target = outgoing_ref.reference.relative_to(&self.base)?;
+            result.push_str(&escape(&text[prev_end..outgoing_ref.start]));
+            result.push_str(&format!(
+                "[{}]({})",
+                escape(&text[outgoing_ref.start..outgoing_ref.end]),
+                href(&self.act_url_pattern, self.act, &target)
+            ));
+            prev_end = outgoing_ref.end;
+        }
+        result.push_str(&escape(&text[prev_end..]));
+        Ok(result)
+    }
+}
+
+impl Default for MarkdownOutputParams {
+    fn default() -> Self {
+        Self::new(DEFAULT_ACT_URL_PATTERN)
+    }
+}
+
+/// Escape the characters that would be interpreted as inline formatting
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>') {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Escape the beginning of a line that would otherwise start a list, heading, etc.
+/// E.g. the text of numeric points ("1. ...") would start an ordered list.
+fn escape_block_start(line: &str) -> String {
+    if line.starts_with(['-', '+', '#', '=', '|']) {
+        return format!("\\{line}");
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 && line[digits..].starts_with(['.', ')']) {
+        format!("{}\\{}", &line[..digits], &line[digits..])
+    } else {
+        line.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::singleton_yaml;
+
+    const TEST_ACT: &str = r#"
+        identifier:
+          year: 2012
+          number: 100
+        subject: A tesztelésről
+        preamble: ""
+        publication_date: 2012-07-13
+        children:
+        - StructuralElement:
+            identifier: "1"
+            title: Általános rendelkezések
+            element_type: Chapter
+        - Subtitle:
+            identifier: "1"
+            title: Fogalmak
+        - Article:
+            identifier: "1"
+            title: Fogalmak
+            children:
+            - body:
+                intro: "E törvény alkalmazásában"
+                children:
+                  AlphabeticPoint:
+                  - identifier: a
+                    body: "teszt: a 2. § szerinti *vizsgálat*,"
+                    semantic_info:
+                      outgoing_references:
+                      - start: 9
+                        end: 14
+                        reference:
+                          article: "2"
+                  - identifier: b
+                    body:
+                      intro: "próba:"
+                      children:
+                        NumericSubpoint:
+                        - identifier: "1"
+                          body: "ellenőrzés,"
+                        - identifier: "2"
+                          body: "kísérlet"
+                      wrap_up: "együttesen."
+        - Article:
+            identifier: "2"
+            children:
+            - identifier: "1"
+              body:
+                intro: "A Ptk. 1:3. §-a helyébe a következő rendelkezés lép:"
+                children:
+                  BlockAmendment:
+                    children:
+                      Paragraph:
+                      - identifier: "2"
+                        body:
+                          intro: "Új szöveg"
+                          children:
+                            NumericPoint:
+                            - identifier: "1"
+                              body: "első,"
+                            - identifier: "2"
+                              body: "második."
+              semantic_info:
+                outgoing_references:
+                - start: 2
+                  end: 16
+                  reference:
+                    act:
+                      year: 2013
+                      number: 5
+                    article: "1:3"
+                special_phrase:
+                  BlockAmendment:
+                    position:
+                      act:
+                        year: 2013
+                        number: 5
+                      article: "1:3"
+                      paragraph: "2"
+                    pure_insertion: false
+        attachments:
+        - identifier: "1"
+          title: A teszt lépései
+          lines:
+          - 1. Előkészítés
+          - 2. Végrehajtás
+          children:
+            NumericPoint:
+            - identifier: "1"
+              body: Előkészítés
+            - identifier: "2"
+              body: Végrehajtás
+        - identifier: "2"
+          lines:
+          - Táblázat
+    "#;
+
+    const EXPECTED_OUTPUT: &str = r#"# 2012. évi C. törvény
+
+**A tesztelésről**
+
+## <a id="chp_1"></a>I. FEJEZET – Általános rendelkezések
+
+### <a id="chp_1__subtitle_1"></a>1. Fogalmak
+
+#### <a id="art_1"></a>1. § \[Fogalmak\]
+
+E törvény alkalmazásában
+
+- <a id="art_1__point_a"></a>a) teszt: a [2. §](#art_2) szerinti \*vizsgálat\*,
+- <a id="art_1__point_b"></a>b) próba:
+  - <a id="art_1__point_b__subpoint_1"></a>1. ellenőrzés,
+  - <a id="art_1__point_b__subpoint_2"></a>2. kísérlet
+
+  együttesen.
+
+#### <a id="art_2"></a>2. §
+
+<a id="art_2__para_1"></a>(1) A [Ptk. 1:3. §-a](2013.%20évi%20V.%20törvény.md#art_1-3) helyébe a következő rendelkezés lép:
+
+>
+> <a id="art_2__para_1__qstr_1__art_1-3__para_2"></a>(2) Új szöveg
+>
+> - <a id="art_2__para_1__qstr_1__art_1-3__para_2__point_1"></a>1. első,
+> - <a id="art_2__para_1__qstr_1__art_1-3__para_2__point_2"></a>2. második.
+
+
+## <a id="att_1"></a>1. melléklet – A teszt lépései
+
+- <a id="att_1__point_1"></a>1. Előkészítés
+- <a id="att_1__point_2"></a>2. Végrehajtás
+
+## <a id="att_2"></a>2. melléklet
+
+```
+Táblázat
+```
+"#;
+
+    #[test]
+    fn test_markdown_output() {
+        let act: Act = singleton_yaml::from_str(TEST_ACT).unwrap();
+        let mut output = Vec::new();
+        act.write_as_markdown(&mut output, MarkdownOutputParams::default())
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, EXPECTED_OUTPUT);
+    }
+
+    #[test]
+    fn test_anchors_match_element_ids() {
+        let act: Act = singleton_yaml::from_str(TEST_ACT).unwrap();
+        let mut output = Vec::new();
+        act.write_as_markdown(&mut output, MarkdownOutputParams::default())
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        for (id, _) in act.elements().unwrap() {
+            let fragment = format!(r#"<a id="{}"></a>"#, id.anchor());
+            assert!(
+                output.contains(&fragment),
+                "{fragment} not found in\n{output}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_escape_block_start() {
+        assert_eq!(escape_block_start("1. pont"), "1\\. pont");
+        assert_eq!(escape_block_start("12) pont"), "12\\) pont");
+        assert_eq!(escape_block_start("- kötőjel"), "\\- kötőjel");
+        assert_eq!(escape_block_start("2012. évi"), "2012\\. évi");
+        assert_eq!(escape_block_start("(1) bekezdés"), "(1) bekezdés");
+    }
+}
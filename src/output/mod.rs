@@ -14,14 +14,21 @@
 // You should have received a copy of the GNU General Public License
 // along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
 
+pub mod akoma_ntoso;
+pub mod html;
+pub mod markdown;
 pub mod text;
 
 use std::io::Write;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use serde::Serialize;
 
-use self::text::{TextOutput, TextOutputParams};
+use self::{
+    html::HtmlOutputParams,
+    markdown::{MarkdownOutput, MarkdownOutputParams},
+    text::{TextOutput, TextOutputParams},
+};
 use crate::{
     parser::{mk_act_section::ActRawText, pdf::PageOfLines},
     structure::Act,
@@ -43,6 +50,15 @@ pub enum OutputFormat {
     /// YAML output
     #[clap(alias("yml"))]
     Yaml,
+    /// Akoma Ntoso XML output. Only supported for fully parsed acts
+    #[clap(alias("akn"))]
+    AkomaNtoso,
+    /// HTML output with linked references. Only supported for fully parsed acts
+    #[clap(alias("htm"))]
+    Html,
+    /// Markdown output with linked references. Only supported for fully parsed acts
+    #[clap(alias("md"))]
+    Markdown,
 }
 
 impl Default for OutputFormat {
@@ -56,6 +72,7 @@ pub trait CliOutput: Sized + Serialize {
         self,
         width: usize,
         output_type: OutputFormat,
+        act_url_pattern: Option<&str>,
         target: &mut impl Write,
     ) -> Result<()> {
         match output_type {
@@ -64,6 +81,9 @@ pub trait CliOutput: Sized + Serialize {
             OutputFormat::TestPlain => self.cli_output_plain(width, true, false, target)?,
             OutputFormat::Json => serde_json::to_writer(target, &self)?,
             OutputFormat::Yaml => singleton_yaml::to_writer(target, &self)?,
+            OutputFormat::AkomaNtoso => self.cli_output_akoma_ntoso(target)?,
+            OutputFormat::Html => self.cli_output_html(act_url_pattern, target)?,
+            OutputFormat::Markdown => self.cli_output_markdown(act_url_pattern, target)?,
         };
         Ok(())
     }
@@ -74,6 +94,23 @@ pub trait CliOutput: Sized + Serialize {
         color: bool,
         target: &mut impl Write,
     ) -> Result<()>;
+    fn cli_output_akoma_ntoso(self, _target: &mut impl Write) -> Result<()> {
+        bail!("Akoma Ntoso output is only supported for acts with parsed structure")
+    }
+    fn cli_output_html(
+        self,
+        _act_url_pattern: Option<&str>,
+        _target: &mut impl Write,
+    ) -> Result<()> {
+        bail!("HTML output is only supported for acts with parsed structure")
+    }
+    fn cli_output_markdown(
+        self,
+        _act_url_pattern: Option<&str>,
+        _target: &mut impl Write,
+    ) -> Result<()> {
+        bail!("Markdown output is only supported for acts with parsed structure")
+    }
 }
 
 impl CliOutput for Vec<PageOfLines> {
@@ -126,6 +163,9 @@ impl CliOutput for ActRawText {
     ) -> Result<()> {
         writeln!(target, "Act ID: {} - {}", self.identifier, self.subject)?;
         writeln!(target, "Pub date: {:?}", self.publication_date)?;
+        if let Some(adoption_date) = self.adoption_date {
+            writeln!(target, "Adoption date: {adoption_date:?}")?;
+        }
         writeln!(target)?;
         for line in self.body {
             writeln!(
@@ -134,6 +174,31 @@ impl CliOutput for ActRawText {
                 quick_display_indented_line(&line, testing_tags)
             )?
         }
+        if !self.signatories.is_empty() {
+            writeln!(target)?;
+            writeln!(target, "Signatories:")?;
+            for signatory in self.signatories {
+                writeln!(target, "{} - {}", signatory.name, signatory.role)?
+            }
+        }
+        if !self.attachments.is_empty() {
+            writeln!(target)?;
+            writeln!(target, "Attachments:")?;
+            for line in self.attachments {
+                writeln!(
+                    target,
+                    "{}",
+                    quick_display_indented_line(&line, testing_tags)
+                )?
+            }
+        }
+        if !self.footnotes.is_empty() {
+            writeln!(target)?;
+            writeln!(target, "Footnotes:")?;
+            for footnote in self.footnotes {
+                writeln!(target, "{} {}", footnote.marker, footnote.text)?
+            }
+        }
         Ok(())
     }
 }
@@ -149,6 +214,26 @@ impl CliOutput for Act {
         let params = TextOutputParams::new(width, color).indented();
         self.write_as_text(target, params)
     }
+
+    fn cli_output_akoma_ntoso(self, target: &mut impl Write) -> Result<()> {
+        self.write_as_akoma_ntoso(target)
+    }
+
+    fn cli_output_html(self, act_url_pattern: Option<&str>, target: &mut impl Write) -> Result<()> {
+        let params =
+            HtmlOutputParams::new(act_url_pattern.unwrap_or(html::DEFAULT_ACT_URL_PATTERN));
+        self.write_as_html_document(target, params)
+    }
+
+    fn cli_output_markdown(
+        self,
+        act_url_pattern: Option<&str>,
+        target: &mut impl Write,
+    ) -> Result<()> {
+        let params =
+            MarkdownOutputParams::new(act_url_pattern.unwrap_or(markdown::DEFAULT_ACT_URL_PATTERN));
+        self.write_as_markdown(target, params)
+    }
 }
 
 pub fn quick_display_indented_line(l: &IndentedLine, testing_tags: bool) -> String {
@@ -168,3 +253,51 @@ pub fn quick_display_indented_line(l: &IndentedLine, testing_tags: bool) -> Stri
     s.push_str(l.content());
     s
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_ACT: &str = r#"
+        identifier:
+          year: 2012
+          number: 100
+        subject: A tesztelésről
+        preamble: ""
+        publication_date: 2012-07-13
+        children:
+        - Article:
+            identifier: "1"
+            children:
+            - body: "A Ptk. 1:3. §-a nem alkalmazandó."
+              semantic_info:
+                outgoing_references:
+                - start: 2
+                  end: 16
+                  reference:
+                    act:
+                      year: 2013
+                      number: 5
+                    article: "1:3"
+    "#;
+
+    fn cli_output_as_string(format: OutputFormat, act_url_pattern: Option<&str>) -> String {
+        let act: Act = singleton_yaml::from_str(TEST_ACT).unwrap();
+        let mut output = Vec::new();
+        act.cli_output(80, format, act_url_pattern, &mut output)
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_act_url_pattern() {
+        let output = cli_output_as_string(OutputFormat::Html, None);
+        assert!(output.contains(r#"href="2013.%20évi%20V.%20törvény.html#art_1-3""#));
+        let output = cli_output_as_string(OutputFormat::Html, Some("/wiki/{act}"));
+        assert!(output.contains(r#"href="/wiki/2013.5#art_1-3""#));
+        let output = cli_output_as_string(OutputFormat::Markdown, None);
+        assert!(output.contains("(2013.%20évi%20V.%20törvény.md#art_1-3)"));
+        let output = cli_output_as_string(OutputFormat::Markdown, Some("/wiki/{act}"));
+        assert!(output.contains("(/wiki/2013.5#art_1-3)"));
+    }
+}
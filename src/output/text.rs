@@ -22,7 +22,7 @@ use crate::{
     identifier::IdentifierCommon,
     semantic_info::{OutgoingReference, SemanticInfo},
     structure::{
-        Act, ActChild, AlphabeticPointChildren, AlphabeticSubpointChildren, Article,
+        Act, ActChild, AlphabeticPointChildren, AlphabeticSubpointChildren, Article, Attachment,
         BlockAmendment, BlockAmendmentChildren, ChildrenCommon, NumericPointChildren,
         NumericSubpointChildren, ParagraphChildren, QuotedBlock, SAEBody, SAEHeaderString,
         StructuralBlockAmendment, StructuralElement, SubArticleElement, Subtitle,
@@ -65,6 +65,25 @@ impl TextOutput for Act {
             child.write_as_text(writer, params.clone())?;
             last_was_article = this_is_article;
         }
+        if !self.signatories.is_empty() {
+            params.write_newline(writer)?;
+        }
+        for signatory in &self.signatories {
+            params.write_wrapped_line(
+                writer,
+                &format!("{} s. k., {}", signatory.name, signatory.role),
+            )?;
+        }
+        for attachment in &self.attachments {
+            params.write_newline(writer)?;
+            attachment.write_as_text(writer, params.clone())?;
+        }
+        if !self.footnotes.is_empty() {
+            params.write_newline(writer)?;
+        }
+        for footnote in &self.footnotes {
+            params.write_wrapped_line(writer, &format!("{} {}", footnote.marker, footnote.text))?;
+        }
         Ok(())
     }
 }
@@ -121,6 +140,25 @@ impl TextOutput for Article {
     }
 }
 
+impl TextOutput for Attachment {
+    fn write_as_text(&self, writer: &mut impl Write, mut params: TextOutputParams) -> Result<()> {
+        params.write_wrapped_line(writer, &params.colorize(self.header_string(), ITALIC))?;
+        if !self.title.is_empty() {
+            params.write_wrapped_line(writer, &params.colorize(&self.title, BOLD))?;
+        }
+        if let Some(children) = &self.children {
+            children.write_as_text(writer, params)?;
+        } else {
+            // Tables and such are better left unwrapped
+            for line in &self.lines {
+                params.write_header(writer, line)?;
+                params.write_newline(writer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl<IT, CT> TextOutput for SubArticleElement<IT, CT>
 where
     SubArticleElement<IT, CT>: SAEHeaderString,
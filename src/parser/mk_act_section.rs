@@ -16,38 +16,210 @@
 
 use anyhow::{ensure, Result};
 use chrono::NaiveDate;
-use lazy_regex::{regex_captures, regex_is_match};
+use lazy_regex::{regex, regex_captures, regex_is_match};
+use log::warn;
 use serde::Serialize;
 
-use crate::identifier::ActIdentifier;
+use crate::identifier::{ActIdentifier, DocumentKind};
+use crate::structure::{Footnote, Signatory};
 use crate::util::hun_str::FromHungarianString;
 use crate::util::indentedline::IndentedLine;
 use crate::{parser::pdf::PageOfLines, util::indentedline::EMPTY_LINE};
 
+use super::mk_toc::{parse_mk_toc, TocEntry};
+
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct ActRawText {
     pub identifier: ActIdentifier,
     pub subject: String,
     pub publication_date: NaiveDate,
+    /// Index of the PDF page where the document starts
+    pub first_page: usize,
+    /// Index of the PDF page where the document (including the attachments) ends
+    pub last_page: usize,
     pub body: Vec<IndentedLine>,
+    pub signatories: Vec<Signatory>,
+    /// Everything after the signatures, up to the next document
+    pub attachments: Vec<IndentedLine>,
+    pub footnotes: Vec<Footnote>,
+    /// Parsed from the "A törvényt az Országgyűlés ... fogadta el." footnote
+    pub adoption_date: Option<NaiveDate>,
 }
 
 impl ActRawText {
     pub fn remove_double_empty_lines(&mut self) {
-        let mut i = 1;
-        while i < self.body.len() {
-            if self.body[i - 1].is_empty() && self.body[i].is_empty() {
-                self.body.remove(i);
-            } else {
-                i += 1;
+        remove_double_empty_lines(&mut self.body);
+        remove_double_empty_lines(&mut self.attachments);
+    }
+
+    fn add_footnote(&mut self, footnote: Footnote) {
+        if self.adoption_date.is_none() {
+            self.adoption_date = parse_adoption_date(&footnote.text);
+        }
+        self.footnotes.push(footnote);
+    }
+
+    fn next_footnote_number(&self) -> usize {
+        self.footnotes.iter().filter(|f| f.marker != "*").count() + 1
+    }
+}
+
+/// Parse the names line ("X s. k., Y s. k.,") and the roles line below it.
+/// Roles are in columns under the names, so they are split at the widest gaps.
+fn parse_signatories(
+    names_line: &IndentedLine,
+    roles_line: &IndentedLine,
+) -> Option<Vec<Signatory>> {
+    if !regex_is_match!(r"^(?:\S.*? s\. ?k\.,?\s*)+$", names_line.content()) {
+        return None;
+    }
+    let names: Vec<&str> = regex!(r"(\S.*?) s\. ?k\.,?")
+        .captures_iter(names_line.content())
+        .filter_map(|c| c.get(1))
+        .map(|m| m.as_str())
+        .collect();
+    let roles = split_columns(roles_line, names.len())?;
+    Some(
+        names
+            .into_iter()
+            .zip(roles)
+            .map(|(name, role)| Signatory {
+                name: name.to_owned(),
+                role: role.trim_end_matches(',').to_owned(),
+            })
+            .collect(),
+    )
+}
+
+/// Split the line into `count` columns at the widest gaps between words
+fn split_columns(line: &IndentedLine, count: usize) -> Option<Vec<&str>> {
+    let content = line.content();
+    let mut gaps: Vec<(f64, usize)> = content
+        .char_indices()
+        .enumerate()
+        .filter(|(char_index, (_, c))| *char_index > 0 && *c == ' ')
+        .map(|(char_index, (byte_index, _))| {
+            let char_index = char_index as i64;
+            (
+                line.indent_at(char_index + 1) - line.indent_at(char_index - 1),
+                byte_index,
+            )
+        })
+        .collect();
+    if gaps.len() + 1 < count {
+        return None;
+    }
+    gaps.sort_by(|(gap1, _), (gap2, _)| gap2.total_cmp(gap1));
+    let mut split_points: Vec<usize> = gaps[..count - 1].iter().map(|(_, i)| *i).collect();
+    split_points.sort_unstable();
+    let mut result = Vec::with_capacity(count);
+    let mut column_start = 0;
+    for split_point in split_points {
+        result.push(&content[column_start..split_point]);
+        column_start = split_point + 1;
+    }
+    result.push(&content[column_start..]);
+    Some(result)
+}
+
+fn parse_adoption_date(footnote_text: &str) -> Option<NaiveDate> {
+    let (_, date) = regex_captures!(
+        "az Országgyűlés a (.*) ülésnapján fogadta el",
+        footnote_text
+    )?;
+    NaiveDate::from_hungarian(date).ok()
+}
+
+/// Remove the footnotes from the end of a page.
+///
+/// Footnotes are separated from the text by an empty line, use a smaller font than
+/// the rest of the page, and start with an asterisk, or their number. Numbers have
+/// to be consecutive, starting from `next_number`.
+///
+/// `page_font_sizes` are the font sizes of the lines on the current page. The last
+/// ones belong to the last lines of `lines`.
+fn split_footnotes(
+    lines: &mut Vec<IndentedLine>,
+    page_font_sizes: &[f32],
+    mut next_number: usize,
+) -> Vec<Footnote> {
+    let block_start = match lines.iter().rposition(IndentedLine::is_empty) {
+        Some(i) => i + 1,
+        None => return Vec::new(),
+    };
+    let block_len = lines.len() - block_start;
+    if page_font_sizes.len() < block_len {
+        return Vec::new();
+    }
+    let (text_font_sizes, block_font_sizes) =
+        page_font_sizes.split_at(page_font_sizes.len() - block_len);
+    let text_font_size = match median_font_size(text_font_sizes.iter().copied()) {
+        Some(size) => size,
+        None => return Vec::new(),
+    };
+    if block_font_sizes
+        .iter()
+        .any(|size| *size >= text_font_size - FONT_SIZE_TOLERANCE)
+    {
+        return Vec::new();
+    }
+    let mut result: Vec<Footnote> = Vec::new();
+    for line in &lines[block_start..] {
+        let new_footnote = regex_captures!(r"^(\*|[0-9]{1,3}) (.*)$", line.content())
+            .filter(|(_, marker, _)| *marker == "*" || marker.parse() == Ok(next_number));
+        if let Some((_, marker, text)) = new_footnote {
+            if marker != "*" {
+                next_number += 1;
             }
+            result.push(Footnote {
+                marker: marker.to_owned(),
+                text: text.to_owned(),
+            });
+        } else if let Some(footnote) = result.last_mut() {
+            line.append_to(&mut footnote.text);
+        } else {
+            return Vec::new();
+        }
+    }
+    lines.truncate(block_start);
+    result
+}
+
+fn remove_double_empty_lines(lines: &mut Vec<IndentedLine>) {
+    let mut i = 1;
+    while i < lines.len() {
+        if lines[i - 1].is_empty() && lines[i].is_empty() {
+            lines.remove(i);
+        } else {
+            i += 1;
         }
     }
 }
 
+/// Method of finding the boundaries of the documents in an MK issue
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentationStrategy {
+    /// Act subjects end with an asterisk referring to the adoption date footnote,
+    /// and the body ends with the signatures
+    Markers,
+    /// Documents start at bold headers followed by a bold subject, or at the headers
+    /// of the documents listed in the table of contents, and end at the signatures,
+    /// or at the next document
+    Layout,
+}
+
+impl Default for SegmentationStrategy {
+    fn default() -> Self {
+        Self::Markers
+    }
+}
+
 struct ActExtractor {
     publication_date: NaiveDate,
+    strategy: SegmentationStrategy,
     current_act: ActRawText,
+    /// Font sizes of the lines fed on the current page
+    page_font_sizes: Vec<f32>,
     result: Vec<ActRawText>,
     state: ActExtractionState,
 }
@@ -57,28 +229,74 @@ enum ActExtractionState {
     WaitingForHeaderNewline,
     WaitingForHeader,
     ParsingActSubject,
+    ParsingDecreeSubject,
     BodyBeforeAsteriskFooter,
     BodyAfterAsteriskFooter,
+    Attachments,
 }
 use ActExtractionState::*;
 
 impl ActExtractor {
-    fn new(publication_date: NaiveDate) -> Self {
+    fn new(publication_date: NaiveDate, strategy: SegmentationStrategy) -> Self {
         Self {
             current_act: Default::default(),
+            page_font_sizes: Vec::new(),
             state: WaitingForHeaderNewline,
             publication_date,
+            strategy,
             result: Vec::new(),
         }
     }
 
+    fn feed_item(&mut self, item: &SectionItem) {
+        match item {
+            SectionItem::Line {
+                line,
+                page,
+                font_size,
+            } => {
+                self.page_font_sizes.push(*font_size);
+                let was_in_document = self.is_in_document();
+                self.feed_line(line);
+                if self.is_in_document() {
+                    if !was_in_document {
+                        self.current_act.first_page = *page;
+                    }
+                    self.current_act.last_page = *page;
+                } else if let (Attachments, Some(act)) = (&self.state, self.result.last_mut()) {
+                    if !line.is_empty() {
+                        act.last_page = *page;
+                    }
+                }
+            }
+            // This is where we do away with the "Page" abstraction, and further processing
+            // can only use EMPTY_LINE to have some separation info.
+            SectionItem::PageEnd => {
+                self.end_page();
+                self.feed_line(&EMPTY_LINE);
+            }
+        }
+    }
+
+    fn is_in_document(&self) -> bool {
+        matches!(
+            self.state,
+            ParsingActSubject
+                | ParsingDecreeSubject
+                | BodyBeforeAsteriskFooter
+                | BodyAfterAsteriskFooter
+        )
+    }
+
     fn feed_line(&mut self, line: &IndentedLine) {
         self.state = match self.state {
             WaitingForHeaderNewline => self.wait_for_header_newline(line),
             WaitingForHeader => self.wait_for_header(line),
             ParsingActSubject => self.parse_act_subject(line),
+            ParsingDecreeSubject => self.parse_decree_subject(line),
             BodyBeforeAsteriskFooter => self.parse_body_before_footer(line),
             BodyAfterAsteriskFooter => self.parse_body_after_footer(line),
+            Attachments => self.parse_attachments(line),
         }
     }
 
@@ -91,22 +309,28 @@ impl ActExtractor {
     }
 
     fn wait_for_header(&mut self, line: &IndentedLine) -> ActExtractionState {
-        if let Some((_, year_str, num_str)) = regex_captures!(
-            "^([12][09][0-9][0-9]). évi ([IVXLC]+). törvény",
-            line.content()
-        ) {
-            if let Ok(year) = year_str.parse::<i16>() {
-                if let Some(number) = roman::from(num_str) {
-                    self.current_act.identifier = ActIdentifier { year, number };
-                    return ParsingActSubject;
+        match parse_document_header(line) {
+            Some(identifier) => {
+                self.current_act.identifier = identifier;
+                if identifier.is_act() {
+                    ParsingActSubject
+                } else {
+                    ParsingDecreeSubject
                 }
             }
+            None => WaitingForHeaderNewline,
         }
-        WaitingForHeaderNewline
     }
 
     fn parse_act_subject(&mut self, line: &IndentedLine) -> ActExtractionState {
         let subject = &mut self.current_act.subject;
+        // In MK, the subject is bold, and the body is not.
+        if self.strategy == SegmentationStrategy::Layout
+            && !subject.is_empty()
+            && (line.is_empty() || !line.is_bold())
+        {
+            return self.parse_body_before_footer(line);
+        }
         line.append_to(subject);
 
         // TODO: this is a huge hack, because we depend on there always being a footer about
@@ -120,18 +344,32 @@ impl ActExtractor {
         ParsingActSubject
     }
 
+    fn parse_decree_subject(&mut self, line: &IndentedLine) -> ActExtractionState {
+        // Decrees usually don't have an asterisk footer, but the subject is
+        // in bold, while the body is not.
+        let subject = &mut self.current_act.subject;
+        if line.is_bold() && !line.is_empty() {
+            line.append_to(subject);
+            if subject.ends_with('*') {
+                subject.pop();
+                return BodyBeforeAsteriskFooter;
+            }
+            ParsingDecreeSubject
+        } else if line.is_empty() {
+            if subject.is_empty() {
+                ParsingDecreeSubject
+            } else {
+                BodyAfterAsteriskFooter
+            }
+        } else {
+            self.parse_body_after_footer(line)
+        }
+    }
+
     fn parse_body_before_footer(&mut self, line: &IndentedLine) -> ActExtractionState {
-        // State to swallow the following footer:
+        // State to wait for the following footer:
         // "* A törvényt az Országgyűlés a 2010. november 22-i ülésnapján fogadta el."
-        let body = &mut self.current_act.body;
-        if line.is_empty()
-            && body.len() > 2
-            && body[body.len() - 2].is_empty()
-            && body[body.len() - 1].content().starts_with('*')
-        {
-            body.pop(); // Pop the asterisk footer, leave the empty line
-            return BodyAfterAsteriskFooter;
-        }
+        // It is moved to the footnotes by end_page()
 
         // There might not be an asterisk footer at all before the end of the act,
         // so check for that too in this state.
@@ -147,40 +385,163 @@ impl ActExtractor {
     fn parse_body_after_footer(&mut self, line: &IndentedLine) -> ActExtractionState {
         let body = &mut self.current_act.body;
         body.push(line.clone());
+        // The end of the document is detected by the signatures of the officials.
         // Example for the actual format of the act footer:
 
         // [EMPTY]
         // Dr. Schmitt Pál s. k.,     Dr. Kövér László s. k.,
         //  köztársasági elnök        az Országgyűlés elnöke
-        if body.len() > 4
-            && body[body.len() - 3].is_empty()
-            && (body.last().unwrap().content() == "köztársasági elnök az Országgyűlés elnöke"
-                || body.last().unwrap().content() == "köztársasági elnök az Országgyűlés alelnöke"
-                || body.last().unwrap().content()
-                    == "az Országgyűlés elnöke, az Országgyűlés alelnöke,")
+
+        // Decrees and resolutions are usually signed by a single official:
+
+        // [EMPTY]
+        // Orbán Viktor s. k.,
+        // miniszterelnök
+        if body.len() > 2 && !line.is_empty() {
+            // Acts always have an empty line before the signatures
+            let preceded_by_empty_line = body.len() > 3 && body[body.len() - 3].is_empty();
+            if preceded_by_empty_line || !self.current_act.identifier.is_act() {
+                if let Some(signatories) = parse_signatories(&body[body.len() - 2], line) {
+                    body.truncate(body.len() - 2);
+                    while body.last().map(IndentedLine::is_empty) == Some(true) {
+                        body.pop();
+                    }
+                    self.current_act.signatories = signatories;
+                    self.finish_current_act();
+                    return Attachments;
+                }
+            }
+        }
+
+        BodyAfterAsteriskFooter
+    }
+
+    fn parse_attachments(&mut self, line: &IndentedLine) -> ActExtractionState {
+        // The act is already finished at this point, so the lines are added
+        // to the last result directly.
+        let (no_attachments_yet, after_empty_line) = match self.result.last() {
+            Some(act) => (
+                act.attachments.is_empty(),
+                act.attachments.last().map(IndentedLine::is_empty) != Some(false),
+            ),
+            None => return self.wait_for_header_newline(line),
+        };
+        if line.is_empty() && no_attachments_yet {
+            return Attachments;
+        }
+        // Attachments may contain anything, even act identifiers at the start of
+        // lines, but document headers are always bold and come after an empty line.
+        if (no_attachments_yet || line.is_bold()) && after_empty_line {
+            let new_state = self.wait_for_header(line);
+            if !matches!(new_state, WaitingForHeaderNewline) {
+                return new_state;
+            }
+        }
+        // The asterisk footer of a short act that fits on a single page
+        if no_attachments_yet {
+            if let Some((_, text)) = regex_captures!(r"^\* (.*)$", line.content()) {
+                if let Some(act) = self.result.last_mut() {
+                    act.add_footnote(Footnote {
+                        marker: "*".to_owned(),
+                        text: text.to_owned(),
+                    });
+                }
+                return Attachments;
+            }
+        }
+        if no_attachments_yet
+            && !regex_is_match!("^(?:[0-9]+\\. )?[mM]elléklet az? ", line.content())
         {
-            body.truncate(body.len() - 3);
-            self.current_act.publication_date = self.publication_date;
-            // take() fills self.current_act with defaults, which is exactly what we want.
-            self.result.push(std::mem::take(&mut self.current_act));
+            // No attachments, just some other junk between documents.
             return WaitingForHeaderNewline;
         }
+        if let Some(act) = self.result.last_mut() {
+            act.attachments.push(line.clone());
+        }
+        Attachments
+    }
 
-        BodyAfterAsteriskFooter
+    /// Move the footnotes at the bottom of the page to the act's metadata.
+    /// Should be called before the page separator empty line.
+    fn end_page(&mut self) {
+        if let BodyBeforeAsteriskFooter | BodyAfterAsteriskFooter = self.state {
+            let next_number = self.current_act.next_footnote_number();
+            for footnote in split_footnotes(
+                &mut self.current_act.body,
+                &self.page_font_sizes,
+                next_number,
+            ) {
+                if footnote.marker == "*" {
+                    self.state = BodyAfterAsteriskFooter;
+                }
+                self.current_act.add_footnote(footnote);
+            }
+        }
+        self.page_font_sizes.clear();
+    }
+
+    /// Finish the current document even if its end was not found
+    fn finish_unfinished_act(&mut self) {
+        if let BodyBeforeAsteriskFooter | BodyAfterAsteriskFooter = self.state {
+            while self.current_act.body.last().map(IndentedLine::is_empty) == Some(true) {
+                self.current_act.body.pop();
+            }
+            self.finish_current_act();
+            self.state = Attachments;
+        }
+    }
+
+    fn finish_current_act(&mut self) {
+        self.current_act.publication_date = self.publication_date;
+        // take() fills self.current_act with defaults, which is exactly what we want.
+        self.result.push(std::mem::take(&mut self.current_act));
     }
 }
 
-const ACTS_SECTION_START: &str = "II. Törvények";
+/// Parse the header of an act ("2010. évi CXXX. törvény"), or the header of a
+/// decree or resolution ("A Kormány 123/2013. (IV. 11.) Korm. rendelete")
+fn parse_document_header(line: &IndentedLine) -> Option<ActIdentifier> {
+    if let Some((_, year_str, num_str)) = regex_captures!(
+        "^([12][09][0-9][0-9]). évi ([IVXLC]+). törvény",
+        line.content()
+    ) {
+        if let Ok(year) = year_str.parse::<i16>() {
+            if let Some(number) = roman::from(num_str) {
+                return Some(ActIdentifier {
+                    year,
+                    number,
+                    kind: DocumentKind::Act,
+                });
+            }
+        }
+    }
+    // Decree and resolution headers are in the form of
+    // "A Kormány 123/2013. (IV. 11.) Korm. rendelete" or
+    // "A belügyminiszter 12/2013. (IV. 11.) BM rendelete"
+    if line.is_bold() {
+        if let Some((_, id_str)) = regex_captures!(
+            r"^Az? .*?([0-9]+/[12][09][0-9][0-9]\. \([IVX]+\. [0-9]{1,2}\.\) \S+ (?:rendelet|határozat))[ea]$",
+            line.content()
+        ) {
+            return id_str.parse().ok();
+        }
+    }
+    None
+}
 
 // These are all prefixes, because there are various ways to line break the longer ones
-const ACT_SECTION_STOPS: &[&str] = &[
+const DOCUMENT_SECTION_STARTS: &[&str] = &[
+    "II. Törvények",
     "III. Kormányrendeletek",
     "IV. A Magyar Nemzeti Bank elnökének rendeletei",
     "V. A Kormány tagjainak rendeletei",
+    "IX. Határozatok Tára",
+];
+
+const DOCUMENT_SECTION_STOPS: &[&str] = &[
     "VI. Az Alkotmánybíróság határozatai",
     "VII. A Kúria határozatai",
     // TODO: VIII. ????
-    "IX. Határozatok Tára",
 ];
 
 fn parse_mk_cover_page(page: &PageOfLines) -> Result<NaiveDate> {
@@ -203,29 +564,37 @@ fn parse_mk_cover_page(page: &PageOfLines) -> Result<NaiveDate> {
     NaiveDate::from_hungarian(page.lines[3].content())
 }
 
-fn line_is_act_section_start(line: &IndentedLine) -> bool {
-    line.is_bold() && line.content() == ACTS_SECTION_START
+fn line_is_document_section_start(line: &IndentedLine) -> bool {
+    line.is_bold()
+        && DOCUMENT_SECTION_STARTS
+            .iter()
+            .any(|pat| line.content().starts_with(pat))
 }
 
-fn line_is_act_section_end(line: &IndentedLine) -> bool {
+fn line_is_document_section_end(line: &IndentedLine) -> bool {
     line.is_bold()
-        && ACT_SECTION_STOPS
+        && DOCUMENT_SECTION_STOPS
             .iter()
             .any(|pat| line.content().starts_with(pat))
 }
 
-pub fn parse_mk_pages_into_acts(pages: &[PageOfLines]) -> Result<Vec<ActRawText>> {
-    ensure!(
-        pages.len() >= 2,
-        "Magyar Közlöny PDFs should have at least 2 pages",
-    );
-    let publication_date = parse_mk_cover_page(&pages[0])?;
+/// A line in the document sections of an MK issue
+#[derive(Debug, Clone, Copy)]
+enum SectionItem<'a> {
+    Line {
+        line: &'a IndentedLine,
+        page: usize,
+        font_size: f32,
+    },
+    PageEnd,
+}
 
-    let mut extractor = ActExtractor::new(publication_date);
+fn document_section_items(pages: &[PageOfLines]) -> Vec<SectionItem<'_>> {
+    let mut result = Vec::new();
     let mut extracting = false;
     let mut seen_a_proper_header = false;
-    for page in pages {
-        for line in &page.lines {
+    for (page_index, page) in pages.iter().enumerate() {
+        for (line_index, line) in page.lines.iter().enumerate() {
             // Thanks MK 2021/97 for your no act section error.
             if !seen_a_proper_header
                 && line.is_bold()
@@ -233,24 +602,717 @@ pub fn parse_mk_pages_into_acts(pages: &[PageOfLines]) -> Result<Vec<ActRawText>
             {
                 extracting = true;
             };
-            if line_is_act_section_start(line) {
+            if line_is_document_section_start(line) {
                 extracting = true;
                 seen_a_proper_header = true;
-            } else if line_is_act_section_end(line) {
+            } else if line_is_document_section_end(line) {
                 extracting = false;
                 seen_a_proper_header = true;
             } else if extracting {
-                extractor.feed_line(line);
+                result.push(SectionItem::Line {
+                    line,
+                    page: page_index,
+                    font_size: page.font_sizes.get(line_index).copied().unwrap_or(0.0),
+                });
             }
         }
-        // This is where we do away with the "Page" abstraction, and further processing
-        // can only use EMPTY_LINE to have some separation info.
         if extracting {
-            extractor.feed_line(&EMPTY_LINE);
+            result.push(SectionItem::PageEnd);
         }
     }
-    for act in &mut extractor.result {
+    result
+}
+
+pub fn parse_mk_pages_into_acts(pages: &[PageOfLines]) -> Result<Vec<ActRawText>> {
+    parse_mk_pages_into_acts_using(pages, SegmentationStrategy::default())
+}
+
+pub fn parse_mk_pages_into_acts_using(
+    pages: &[PageOfLines],
+    strategy: SegmentationStrategy,
+) -> Result<Vec<ActRawText>> {
+    ensure!(
+        pages.len() >= 2,
+        "Magyar Közlöny PDFs should have at least 2 pages",
+    );
+    let publication_date = parse_mk_cover_page(&pages[0])?;
+    let items = document_section_items(pages);
+    let mut result = match strategy {
+        SegmentationStrategy::Markers => segment_by_markers(&items, publication_date),
+        SegmentationStrategy::Layout => {
+            let toc = parse_mk_toc(pages).unwrap_or_else(|e| {
+                warn!("Could not parse the table of contents, segmenting without it: {e:?}");
+                Vec::new()
+            });
+            segment_by_layout(&items, &toc, publication_date)
+        }
+    };
+    for act in &mut result {
         act.remove_double_empty_lines();
     }
-    Ok(extractor.result)
+    Ok(result)
+}
+
+fn segment_by_markers(items: &[SectionItem], publication_date: NaiveDate) -> Vec<ActRawText> {
+    let mut extractor = ActExtractor::new(publication_date, SegmentationStrategy::Markers);
+    for item in items {
+        extractor.feed_item(item);
+    }
+    extractor.result
+}
+
+fn segment_by_layout(
+    items: &[SectionItem],
+    toc: &[TocEntry],
+    publication_date: NaiveDate,
+) -> Vec<ActRawText> {
+    let mut header_indices: Vec<usize> = (0..items.len())
+        .filter(|index| is_layout_header(items, *index))
+        .collect();
+    header_indices.extend(headers_from_toc(items, toc, &header_indices));
+    header_indices.sort_unstable();
+    let mut result = Vec::new();
+    for (i, start) in header_indices.iter().enumerate() {
+        let end = header_indices.get(i + 1).copied().unwrap_or(items.len());
+        let mut extractor = ActExtractor::new(publication_date, SegmentationStrategy::Layout);
+        extractor.feed_line(&EMPTY_LINE);
+        for item in &items[*start..end] {
+            extractor.feed_item(item);
+        }
+        extractor.finish_unfinished_act();
+        result.extend(extractor.result);
+    }
+    result
+}
+
+/// Document headers are bold, come after an empty line, and are followed by a
+/// bold subject. Their font is not smaller than the usual text on the page.
+fn is_layout_header(items: &[SectionItem], index: usize) -> bool {
+    let (line, page, font_size) = match items[index] {
+        SectionItem::Line {
+            line,
+            page,
+            font_size,
+        } => (line, page, font_size),
+        SectionItem::PageEnd => return false,
+    };
+    if !line.is_bold() || parse_document_header(line).is_none() {
+        return false;
+    }
+    let subject_is_bold = items[index + 1..].iter().find_map(|item| match item {
+        SectionItem::Line { line, .. } if !line.is_empty() => Some(line.is_bold()),
+        _ => None,
+    }) == Some(true);
+    follows_empty_line(items, index)
+        && subject_is_bold
+        && font_size >= typical_font_size(items, page)
+}
+
+fn follows_empty_line(items: &[SectionItem], index: usize) -> bool {
+    index == 0
+        || match items[index - 1] {
+            SectionItem::Line { line, .. } => line.is_empty(),
+            SectionItem::PageEnd => true,
+        }
+}
+
+/// Headers of the documents in the table of contents that were not found by their
+/// layout, e.g. because their subject is not bold.
+///
+/// The table of contents uses the page numbers of the MK volume, which are not
+/// part of the extracted text. They are converted to PDF pages using the first
+/// document that was found by its layout, and is also listed in the table of contents.
+fn headers_from_toc(
+    items: &[SectionItem],
+    toc: &[TocEntry],
+    layout_headers: &[usize],
+) -> Vec<usize> {
+    let header_at = |index: usize| match items[index] {
+        SectionItem::Line { line, page, .. } => {
+            parse_document_header(line).map(|identifier| (identifier, page))
+        }
+        SectionItem::PageEnd => None,
+    };
+    let found: Vec<(ActIdentifier, usize)> = layout_headers
+        .iter()
+        .filter_map(|index| header_at(*index))
+        .collect();
+    let page_offset = found.iter().find_map(|(identifier, page)| {
+        toc.iter()
+            .find(|entry| entry.identifier == *identifier)
+            .map(|entry| *page as i64 - entry.page as i64)
+    });
+    let page_offset = match page_offset {
+        Some(page_offset) => page_offset,
+        None => return Vec::new(),
+    };
+    let mut result = Vec::new();
+    for entry in toc {
+        if found
+            .iter()
+            .any(|(identifier, _)| *identifier == entry.identifier)
+        {
+            continue;
+        }
+        let expected_page = entry.page as i64 + page_offset;
+        let header = (0..items.len()).find(|index| {
+            follows_empty_line(items, *index)
+                && matches!(
+                    header_at(*index),
+                    Some((identifier, page))
+                        if identifier == entry.identifier && page as i64 == expected_page
+                )
+        });
+        if let Some(header) = header {
+            result.push(header);
+        }
+    }
+    result
+}
+
+/// Some tolerance for rounding errors in the text matrices
+const FONT_SIZE_TOLERANCE: f32 = 0.1;
+
+/// The median font size of the non-empty lines on a page
+fn typical_font_size(items: &[SectionItem], page: usize) -> f32 {
+    let font_sizes = items.iter().filter_map(|item| match item {
+        SectionItem::Line {
+            line,
+            page: line_page,
+            font_size,
+        } if *line_page == page && !line.is_empty() => Some(*font_size),
+        _ => None,
+    });
+    median_font_size(font_sizes).map_or(0.0, |size| size - FONT_SIZE_TOLERANCE)
+}
+
+/// The median of the font sizes, ignoring empty lines (which have a size of 0.0)
+fn median_font_size(font_sizes: impl Iterator<Item = f32>) -> Option<f32> {
+    let mut font_sizes: Vec<f32> = font_sizes.filter(|size| *size > 0.0).collect();
+    if font_sizes.is_empty() {
+        return None;
+    }
+    font_sizes.sort_unstable_by(|f1, f2| f1.total_cmp(f2));
+    Some(font_sizes[font_sizes.len() / 2])
+}
+
+/// Where and how long a document is, according to a segmentation strategy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DocumentExtent {
+    pub first_page: usize,
+    pub last_page: usize,
+    pub body_lines: usize,
+    pub attachment_lines: usize,
+}
+
+impl From<&ActRawText> for DocumentExtent {
+    fn from(act: &ActRawText) -> Self {
+        Self {
+            first_page: act.first_page,
+            last_page: act.last_page,
+            body_lines: act.body.len(),
+            attachment_lines: act.attachments.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum SegmentationDifference {
+    OnlyFoundByMarkers {
+        identifier: ActIdentifier,
+        extent: DocumentExtent,
+    },
+    OnlyFoundByLayout {
+        identifier: ActIdentifier,
+        extent: DocumentExtent,
+    },
+    DifferentExtent {
+        identifier: ActIdentifier,
+        markers: DocumentExtent,
+        layout: DocumentExtent,
+    },
+}
+
+/// Run all segmentation strategies, and report the documents where they disagree
+pub fn compare_segmentation_strategies(
+    pages: &[PageOfLines],
+) -> Result<Vec<SegmentationDifference>> {
+    let by_markers = parse_mk_pages_into_acts_using(pages, SegmentationStrategy::Markers)?;
+    let by_layout = parse_mk_pages_into_acts_using(pages, SegmentationStrategy::Layout)?;
+    let mut result = Vec::new();
+    for act in &by_markers {
+        match by_layout.iter().find(|a| a.identifier == act.identifier) {
+            None => result.push(SegmentationDifference::OnlyFoundByMarkers {
+                identifier: act.identifier,
+                extent: act.into(),
+            }),
+            Some(layout_act) => {
+                let (markers, layout) = (DocumentExtent::from(act), layout_act.into());
+                if markers != layout {
+                    result.push(SegmentationDifference::DifferentExtent {
+                        identifier: act.identifier,
+                        markers,
+                        layout,
+                    });
+                }
+            }
+        }
+    }
+    for act in &by_layout {
+        if !by_markers.iter().any(|a| a.identifier == act.identifier) {
+            result.push(SegmentationDifference::OnlyFoundByLayout {
+                identifier: act.identifier,
+                extent: act.into(),
+            });
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(lines: &[&str]) -> Vec<ActRawText> {
+        let mut extractor = ActExtractor::new(
+            NaiveDate::from_ymd_opt(2013, 4, 11).unwrap(),
+            SegmentationStrategy::Markers,
+        );
+        for line in lines {
+            extractor.feed_line(&IndentedLine::from_test_str(line));
+        }
+        extractor.result
+    }
+
+    /// Lines starting with "<SMALL>" are in a smaller font, like footnotes
+    fn extract_pages(pages: &[&[&str]]) -> Vec<ActRawText> {
+        let mut extractor = ActExtractor::new(
+            NaiveDate::from_ymd_opt(2013, 4, 11).unwrap(),
+            SegmentationStrategy::Markers,
+        );
+        for (page_index, page) in pages.iter().enumerate() {
+            for line in *page {
+                let (line, font_size) = match line.strip_prefix("<SMALL>") {
+                    Some(line) => (IndentedLine::from_test_str(line), 7.0),
+                    None => (IndentedLine::from_test_str(line), 9.0),
+                };
+                extractor.feed_item(&SectionItem::Line {
+                    font_size: if line.is_empty() { 0.0 } else { font_size },
+                    line: &line,
+                    page: page_index,
+                });
+            }
+            extractor.feed_item(&SectionItem::PageEnd);
+        }
+        extractor.result
+    }
+
+    #[test]
+    fn test_decree_extraction() {
+        let result = extract(&[
+            "",
+            "<BOLD>A Kormány 123/2013. (IV. 11.) Korm. rendelete",
+            "<BOLD>a tesztelés részletes szabályairól",
+            "",
+            "A Kormány az Alaptörvény 15. cikk (3) bekezdésében meghatározott",
+            "eredeti jogalkotói hatáskörében a következőket rendeli el:",
+            "1. § Ez a rendelet a kihirdetését követő napon lép hatályba.",
+            "",
+            "Orbán Viktor s. k.,",
+            "miniszterelnök",
+            "",
+            "<BOLD>A belügyminiszter 12/2013. (IV. 11.) BM rendelete",
+            "<BOLD>a tesztelés további szabályairól",
+            "1. § Ez a rendelet a kihirdetését követő napon lép hatályba.",
+            "",
+            "Dr. Pintér Sándor s. k.,",
+            "belügyminiszter",
+        ]);
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            result[0].identifier,
+            "123/2013. (IV. 11.) Korm. rendelet".parse().unwrap()
+        );
+        assert_eq!(result[0].subject, "a tesztelés részletes szabályairól");
+        assert_eq!(result[0].body.len(), 3);
+        assert_eq!(
+            result[0].body[2].content(),
+            "1. § Ez a rendelet a kihirdetését követő napon lép hatályba."
+        );
+        assert_eq!(
+            result[1].identifier,
+            "12/2013. (IV. 11.) BM rendelet".parse().unwrap()
+        );
+        assert_eq!(result[1].body.len(), 1);
+        assert_eq!(result[1].publication_date, result[0].publication_date);
+        assert_eq!(
+            result[1].signatories,
+            vec![Signatory {
+                name: "Dr. Pintér Sándor".to_owned(),
+                role: "belügyminiszter".to_owned(),
+            }]
+        );
+    }
+
+    fn signatories(names: &str, roles: &str) -> Option<Vec<(String, String)>> {
+        Some(
+            parse_signatories(
+                &IndentedLine::from_test_str(names),
+                &IndentedLine::from_test_str(roles),
+            )?
+            .into_iter()
+            .map(|s| (s.name, s.role))
+            .collect(),
+        )
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Option<Vec<(String, String)>> {
+        Some(
+            expected
+                .iter()
+                .map(|(name, role)| (name.to_string(), role.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_signatory_parsing() {
+        assert_eq!(
+            signatories(
+                "Dr. Schmitt Pál s. k.,     Dr. Kövér László s. k.,",
+                " köztársasági elnök        az Országgyűlés elnöke",
+            ),
+            pairs(&[
+                ("Dr. Schmitt Pál", "köztársasági elnök"),
+                ("Dr. Kövér László", "az Országgyűlés elnöke"),
+            ])
+        );
+        assert_eq!(
+            signatories(
+                "Kövér László s. k.,     Dr. Latorcai János s. k.,",
+                "az Országgyűlés elnöke,     az Országgyűlés alelnöke,",
+            ),
+            pairs(&[
+                ("Kövér László", "az Országgyűlés elnöke"),
+                ("Dr. Latorcai János", "az Országgyűlés alelnöke"),
+            ])
+        );
+        assert_eq!(
+            signatories("Orbán Viktor s. k.,", "miniszterelnök"),
+            pairs(&[("Orbán Viktor", "miniszterelnök")])
+        );
+        assert_eq!(
+            signatories(
+                "1. § Ez a rendelet a kihirdetését követő napon lép hatályba.",
+                "miniszterelnök"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_attachment_extraction() {
+        let result = extract(&[
+            "",
+            "<BOLD>A Kormány 123/2013. (IV. 11.) Korm. rendelete",
+            "<BOLD>a tesztelés részletes szabályairól",
+            "",
+            "1. § Ez a rendelet a kihirdetését követő napon lép hatályba.",
+            "",
+            "Orbán Viktor s. k.,",
+            "miniszterelnök",
+            "",
+            "1. melléklet a 123/2013. (IV. 11.) Korm. rendelethez",
+            "<BOLD>A díjak",
+            "",
+            "2012. évi I. törvény szerinti díj 100 Ft",
+            "",
+            "2. melléklet a 123/2013. (IV. 11.) Korm. rendelethez",
+            "",
+            "<BOLD>A belügyminiszter 12/2013. (IV. 11.) BM rendelete",
+            "<BOLD>a tesztelés további szabályairól",
+            "1. § Ez a rendelet a kihirdetését követő napon lép hatályba.",
+            "",
+            "Dr. Pintér Sándor s. k.,",
+            "belügyminiszter",
+            "",
+            "Some junk that is not an attachment",
+        ]);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].body.len(), 1);
+        let attachment_lines: Vec<&str> =
+            result[0].attachments.iter().map(|l| l.content()).collect();
+        assert_eq!(
+            attachment_lines,
+            vec![
+                "1. melléklet a 123/2013. (IV. 11.) Korm. rendelethez",
+                "A díjak",
+                "",
+                "2012. évi I. törvény szerinti díj 100 Ft",
+                "",
+                "2. melléklet a 123/2013. (IV. 11.) Korm. rendelethez",
+                "",
+            ]
+        );
+        assert_eq!(
+            result[1].identifier,
+            "12/2013. (IV. 11.) BM rendelet".parse().unwrap()
+        );
+        assert!(result[1].attachments.is_empty());
+    }
+
+    #[test]
+    fn test_footnote_extraction() {
+        let result = extract_pages(&[
+            &[
+                "",
+                "2013. évi XII. törvény",
+                "a tesztelésről*",
+                "",
+                "1. § Ez a törvény a tesztelésről szól.1",
+                "",
+                "<SMALL>* A törvényt az Országgyűlés a 2013. március 4-i ülésnapján fogadta el.",
+                "<SMALL>1 Ez egy lábjegyzet, amely",
+                "<SMALL>két sorból áll.",
+            ],
+            &[
+                "2. § Ez a törvény a kihirdetését követő napon lép hatályba.",
+                "",
+                "Áder János s. k.,     Kövér László s. k.,",
+                "köztársasági elnök     az Országgyűlés elnöke",
+                "",
+                "2013. évi XIII. törvény",
+                "a további tesztelésről*",
+                "",
+                "1. § Ez a törvény a kihirdetését követő napon lép hatályba.",
+                "",
+                "Áder János s. k.,     Kövér László s. k.,",
+                "köztársasági elnök     az Országgyűlés elnöke",
+                "",
+                "<SMALL>* A törvényt az Országgyűlés a 2013. március 5-i ülésnapján fogadta el.",
+            ],
+        ]);
+        assert_eq!(result.len(), 2);
+        let body_lines: Vec<&str> = result[0]
+            .body
+            .iter()
+            .map(|l| l.content())
+            .filter(|l| !l.is_empty())
+            .collect();
+        assert_eq!(
+            body_lines,
+            vec![
+                "1. § Ez a törvény a tesztelésről szól.1",
+                "2. § Ez a törvény a kihirdetését követő napon lép hatályba.",
+            ]
+        );
+        assert_eq!(
+            result[0].footnotes,
+            vec![
+                Footnote {
+                    marker: "*".to_owned(),
+                    text: "A törvényt az Országgyűlés a 2013. március 4-i ülésnapján fogadta el."
+                        .to_owned(),
+                },
+                Footnote {
+                    marker: "1".to_owned(),
+                    text: "Ez egy lábjegyzet, amely két sorból áll.".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(result[0].adoption_date, NaiveDate::from_ymd_opt(2013, 3, 4));
+        assert_eq!(result[1].footnotes.len(), 1);
+        assert_eq!(result[1].adoption_date, NaiveDate::from_ymd_opt(2013, 3, 5));
+        assert!(result[1].attachments.is_empty());
+    }
+
+    #[test]
+    fn test_numbered_body_line_is_not_a_footnote() {
+        let result = extract_pages(&[
+            &[
+                "",
+                "2013. évi XII. törvény",
+                "a tesztelésről*",
+                "",
+                "1. § A tesztelés díja:",
+                "",
+                "1 forint naponta.",
+                "",
+                "<SMALL>* A törvényt az Országgyűlés a 2013. március 4-i ülésnapján fogadta el.",
+            ],
+            &[
+                "2. § A miniszter tesztel.",
+                "",
+                "1 nap után a miniszter pihen.",
+            ],
+            &[
+                "3. § Ez a törvény a kihirdetését követő napon lép hatályba.",
+                "",
+                "Áder János s. k.,     Kövér László s. k.,",
+                "köztársasági elnök     az Országgyűlés elnöke",
+            ],
+        ]);
+        assert_eq!(result.len(), 1);
+        let body_lines: Vec<&str> = result[0]
+            .body
+            .iter()
+            .map(|l| l.content())
+            .filter(|l| !l.is_empty())
+            .collect();
+        assert_eq!(
+            body_lines,
+            vec![
+                "1. § A tesztelés díja:",
+                "1 forint naponta.",
+                "2. § A miniszter tesztel.",
+                "1 nap után a miniszter pihen.",
+                "3. § Ez a törvény a kihirdetését követő napon lép hatályba.",
+            ]
+        );
+        assert_eq!(result[0].footnotes.len(), 1);
+    }
+
+    fn test_page(lines: &[&str]) -> PageOfLines {
+        let lines: Vec<IndentedLine> = lines
+            .iter()
+            .map(|line| IndentedLine::from_test_str(line))
+            .collect();
+        PageOfLines {
+            font_sizes: lines
+                .iter()
+                .map(|line| if line.is_empty() { 0.0 } else { 9.0 })
+                .collect(),
+            lines,
+            tables: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_layout_segmentation() {
+        let pages = [
+            test_page(&[
+                "MAGYAR KÖZLÖNY 52. szám",
+                "",
+                "MAGYARORSZÁG HIVATALOS LAPJA",
+                "2013. április 11., csütörtök",
+            ]),
+            test_page(&[
+                "<BOLD>II. Törvények",
+                "",
+                "<BOLD>2013. évi XII. törvény",
+                "<BOLD>a tesztelésről",
+                "",
+                "1. § Ez a törvény a kihirdetését követő napon lép hatályba.",
+                "",
+                "Áder János s. k.,     Kövér László s. k.,",
+                "köztársasági elnök     az Országgyűlés elnöke",
+                "",
+                "<BOLD>2013. évi XIII. törvény",
+                "<BOLD>a további tesztelésről",
+                "",
+                "1. § Ez a törvény a kihirdetését követő napon lép hatályba.",
+            ]),
+            test_page(&[
+                "2. § Ez a törvény a 2012. évi I. törvényt módosítja.",
+                "",
+                "<BOLD>III. Kormányrendeletek",
+                "",
+                "<BOLD>A Kormány 123/2013. (IV. 11.) Korm. rendelete",
+                "<BOLD>a tesztelés részletes szabályairól",
+                "",
+                "1. § Ez a rendelet a kihirdetését követő napon lép hatályba.",
+                "",
+                "Orbán Viktor s. k.,",
+                "miniszterelnök",
+            ]),
+        ];
+        let result = parse_mk_pages_into_acts_using(&pages, SegmentationStrategy::Layout).unwrap();
+        let subjects: Vec<&str> = result.iter().map(|act| act.subject.as_str()).collect();
+        assert_eq!(
+            subjects,
+            vec![
+                "a tesztelésről",
+                "a további tesztelésről",
+                "a tesztelés részletes szabályairól"
+            ]
+        );
+        assert_eq!(result[0].signatories.len(), 2);
+        let body_lines: Vec<&str> = result[1]
+            .body
+            .iter()
+            .map(|l| l.content())
+            .filter(|l| !l.is_empty())
+            .collect();
+        assert_eq!(
+            body_lines,
+            vec![
+                "1. § Ez a törvény a kihirdetését követő napon lép hatályba.",
+                "2. § Ez a törvény a 2012. évi I. törvényt módosítja.",
+            ]
+        );
+        assert_eq!((result[1].first_page, result[1].last_page), (1, 2));
+        assert_eq!(
+            result[2].identifier,
+            "123/2013. (IV. 11.) Korm. rendelet".parse().unwrap()
+        );
+        assert_eq!(result[2].first_page, 2);
+
+        // None of the acts have the asterisk footnote
+        let differences = compare_segmentation_strategies(&pages).unwrap();
+        assert_eq!(differences.len(), 3);
+        assert!(differences
+            .iter()
+            .all(|d| matches!(d, SegmentationDifference::OnlyFoundByLayout { .. })));
+    }
+
+    #[test]
+    fn test_layout_segmentation_with_toc() {
+        let pages = [
+            test_page(&[
+                "MAGYAR KÖZLÖNY 52. szám",
+                "",
+                "MAGYARORSZÁG HIVATALOS LAPJA",
+                "2013. április 11., csütörtök",
+                "",
+                "<BOLD>Tartalomjegyzék",
+                "",
+                "2013. évi XII. törvény A tesztelésről 2101",
+                "",
+                "2013. évi XIII. törvény A további tesztelésről 2102",
+            ]),
+            test_page(&[
+                "<BOLD>II. Törvények",
+                "",
+                "<BOLD>2013. évi XII. törvény",
+                "<BOLD>a tesztelésről",
+                "",
+                "1. § Ez a törvény a kihirdetését követő napon lép hatályba.",
+                "",
+                "2013. évi XIV. törvény",
+                "a nem létező törvényről",
+            ]),
+            test_page(&[
+                "2013. évi XIII. törvény",
+                "a további tesztelésről",
+                "",
+                "1. § Ez a törvény a kihirdetését követő napon lép hatályba.",
+            ]),
+        ];
+        let result = parse_mk_pages_into_acts_using(&pages, SegmentationStrategy::Layout).unwrap();
+        let identifiers: Vec<String> = result
+            .iter()
+            .map(|act| act.identifier.to_string())
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec!["2013. évi XII. törvény", "2013. évi XIII. törvény"]
+        );
+        assert_eq!(result[1].subject, "a további tesztelésről");
+        assert_eq!(result[1].first_page, 2);
+        // Not listed in the table of contents, so it is part of the body of the first act
+        assert!(result[0]
+            .body
+            .iter()
+            .any(|line| line.content() == "2013. évi XIV. törvény"));
+    }
 }
@@ -0,0 +1,253 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+use lazy_regex::{regex_captures, regex_is_match};
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use super::{mk_act_section::ActRawText, pdf::PageOfLines};
+use crate::identifier::ActIdentifier;
+
+/// A document listed in the table of contents ("Tartalomjegyzék") of an MK issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TocEntry {
+    pub identifier: ActIdentifier,
+    pub title: String,
+    /// The page where the document starts, as numbered in the MK volume
+    pub page: u32,
+}
+
+/// Parse the table of contents on the first pages of an MK issue.
+///
+/// Entries without an act, decree or resolution identifier (e.g. Kúria decisions)
+/// are skipped. Entries that cannot be parsed are skipped with a warning.
+/// Returns an empty list if there is no table of contents.
+pub fn parse_mk_toc(pages: &[PageOfLines]) -> Result<Vec<TocEntry>> {
+    let mut result = Vec::new();
+    let mut in_toc = false;
+    let mut entry_text = String::new();
+    'pages: for page in pages {
+        for line in &page.lines {
+            if line.is_bold() && line.content() == "Tartalomjegyzék" {
+                in_toc = true;
+            } else if !in_toc {
+                continue;
+            } else if line.is_bold() {
+                // Section header of the first document
+                break 'pages;
+            } else if line.is_empty() {
+                finish_entry(&mut entry_text, &mut result)?;
+            } else {
+                line.append_to(&mut entry_text);
+            }
+        }
+        // Long entries may continue on the next page
+        if regex_is_match!(" [0-9]+$", &entry_text) {
+            finish_entry(&mut entry_text, &mut result)?;
+        }
+    }
+    finish_entry(&mut entry_text, &mut result)?;
+    Ok(result)
+}
+
+fn finish_entry(entry_text: &mut String, result: &mut Vec<TocEntry>) -> Result<()> {
+    if !entry_text.is_empty() {
+        result.extend(parse_toc_entry(entry_text)?);
+        entry_text.clear();
+    }
+    Ok(())
+}
+
+fn parse_toc_entry(text: &str) -> Result<Option<TocEntry>> {
+    let (_, text, page) = match regex_captures!("^(.*) ([0-9]+)$", text) {
+        Some(captures) => captures,
+        None => {
+            warn!("No page number in table of contents entry '{text}'");
+            return Ok(None);
+        }
+    };
+    let (identifier, title) = match regex_captures!(
+        r"^([12][0-9]{3}\. évi [IVXLCDM]+\. törvény|[0-9]+/[12][0-9]{3}\. \([IVX]+\. [0-9]{1,2}\.\) \S+ (?:rendelet|határozat)) (.*)$",
+        text
+    ) {
+        Some((_, identifier, title)) => (identifier, title),
+        None => return Ok(None),
+    };
+    Ok(Some(TocEntry {
+        identifier: identifier.parse()?,
+        title: title.to_owned(),
+        page: page.parse()?,
+    }))
+}
+
+/// A disagreement between the table of contents and the documents extracted from an MK issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum TocDifference {
+    /// Listed in the table of contents, but not extracted
+    Missing {
+        identifier: ActIdentifier,
+        page: u32,
+    },
+    /// Extracted, but not listed in the table of contents
+    Extra { identifier: ActIdentifier },
+}
+
+/// Cross-check the result of `parse_mk_pages_into_acts` with the table of contents.
+///
+/// Constitutional Court decisions are never extracted, so they are not reported as missing.
+pub fn compare_with_toc(toc: &[TocEntry], acts: &[ActRawText]) -> Vec<TocDifference> {
+    let mut result = Vec::new();
+    for entry in toc {
+        let is_extracted = !matches!(
+            entry.identifier.kind.issuer(),
+            Some(issuer) if issuer.as_str() == "AB"
+        );
+        if is_extracted && !acts.iter().any(|act| act.identifier == entry.identifier) {
+            result.push(TocDifference::Missing {
+                identifier: entry.identifier,
+                page: entry.page,
+            });
+        }
+    }
+    for act in acts {
+        if !toc.iter().any(|entry| entry.identifier == act.identifier) {
+            result.push(TocDifference::Extra {
+                identifier: act.identifier,
+            });
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::indentedline::IndentedLine;
+
+    fn test_page(lines: &[&str]) -> PageOfLines {
+        let lines: Vec<IndentedLine> = lines
+            .iter()
+            .map(|line| IndentedLine::from_test_str(line))
+            .collect();
+        PageOfLines {
+            font_sizes: vec![9.0; lines.len()],
+            lines,
+            tables: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_toc_parsing() {
+        let pages = [
+            test_page(&[
+                "MAGYAR KÖZLÖNY 88. szám",
+                "",
+                "MAGYARORSZÁG HIVATALOS LAPJA",
+                "2022. május 26., csütörtök",
+                "",
+                "<BOLD>Tartalomjegyzék",
+                "",
+                "2022. évi V. törvény A veszélyhelyzet megszűnésével összefüggő szabályozási kérdésekről 3591",
+                "",
+                "185/2022. (V. 26.) Korm. rendelet A politikai szolgálati jogviszonyt és a kormányzati",
+                "                                  szolgálati jogviszonyt érintő egyes veszélyhelyzeti",
+            ]),
+            test_page(&[
+                "                                  szabályokról 3628",
+                "",
+                "1/2022. JEH határozat Jogegységi határozat 3650",
+                "",
+                "190/2022. (V. 26.) KE határozat Egyetemi tanári kinevezésről 3656",
+                "<BOLD>II. Törvények",
+                "",
+                "<BOLD>2022. évi V. törvény",
+            ]),
+        ];
+        let toc = parse_mk_toc(&pages).unwrap();
+        let summary: Vec<(String, &str, u32)> = toc
+            .iter()
+            .map(|entry| {
+                (
+                    entry.identifier.to_string(),
+                    entry.title.as_str(),
+                    entry.page,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "2022. évi V. törvény".to_owned(),
+                    "A veszélyhelyzet megszűnésével összefüggő szabályozási kérdésekről",
+                    3591
+                ),
+                (
+                    "185/2022. (V. 26.) Korm. rendelet".to_owned(),
+                    "A politikai szolgálati jogviszonyt és a kormányzati szolgálati jogviszonyt érintő egyes veszélyhelyzeti szabályokról",
+                    3628
+                ),
+                (
+                    "190/2022. (V. 26.) KE határozat".to_owned(),
+                    "Egyetemi tanári kinevezésről",
+                    3656
+                ),
+            ]
+        );
+
+        let acts: Vec<ActRawText> = ["2022. évi V. törvény", "2022. évi VI. törvény"]
+            .iter()
+            .map(|id| ActRawText {
+                identifier: id.parse().unwrap(),
+                ..Default::default()
+            })
+            .collect();
+        assert_eq!(
+            compare_with_toc(&toc, &acts),
+            vec![
+                TocDifference::Missing {
+                    identifier: toc[1].identifier,
+                    page: 3628
+                },
+                TocDifference::Missing {
+                    identifier: toc[2].identifier,
+                    page: 3656
+                },
+                TocDifference::Extra {
+                    identifier: acts[1].identifier
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unparseable_toc_entry() {
+        let pages = [test_page(&[
+            "<BOLD>Tartalomjegyzék",
+            "",
+            "2022. évi V. törvény A veszélyhelyzet megszűnésével összefüggő szabályozási kérdésekről 3591",
+            "",
+            "Határozatok",
+            "",
+            "190/2022. (V. 26.) KE határozat Egyetemi tanári kinevezésről 3656",
+            "<BOLD>II. Törvények",
+        ])];
+        let toc = parse_mk_toc(&pages).unwrap();
+        let pages: Vec<u32> = toc.iter().map(|entry| entry.page).collect();
+        assert_eq!(pages, vec![3591, 3656]);
+    }
+}
@@ -15,6 +15,7 @@
 // along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
 
 pub mod mk_act_section;
+pub mod mk_toc;
 pub mod pdf;
 pub mod semantic_info;
 pub mod structure;
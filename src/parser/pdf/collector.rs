@@ -19,6 +19,7 @@ use log::debug;
 
 use super::{
     font::{FastFont, ToUnicodeResult},
+    table::Ruling,
     textstate::TextState,
     util::fix_character_coding_quirks,
     CropBox,
@@ -26,19 +27,22 @@ use super::{
 
 const DEFAULT_WIDTH_OF_SPACE: f32 = 0.25;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct PositionedChar {
     pub x: f32,
     pub y: f32,
     pub width: f32,
     pub width_of_space: f32,
     pub bold: bool,
+    /// Effective font size, in points
+    pub font_size: f32,
     pub content: char,
 }
 
 #[derive(Debug, Default)]
 pub struct CharCollector {
     pub chars: Vec<PositionedChar>,
+    pub rulings: Vec<Ruling>,
     width_of_space: f32,
     current_font_is_bold: bool,
     crop: CropBox,
@@ -70,6 +74,7 @@ impl CharCollector {
                 width_of_space,
                 content,
                 bold: self.current_font_is_bold,
+                font_size: scaling,
             });
         }
         Ok(())
@@ -128,4 +133,12 @@ impl CharCollector {
             .map_or(false, |n| n.contains("bold") || n.contains("Bold"));
         Ok(())
     }
+
+    pub fn render_rulings(&mut self, rulings: Vec<Ruling>) {
+        self.rulings.extend(
+            rulings
+                .iter()
+                .filter_map(|ruling| self.crop.clip_ruling(ruling)),
+        );
+    }
 }
@@ -19,12 +19,19 @@ mod collector;
 mod font;
 mod page;
 mod page_of_lines;
+mod path;
+mod table;
 mod textstate;
 mod util;
 
 use anyhow::Result;
 
-use self::{collector::CharCollector, font::FontCache, page::PageRenderer};
+use self::{
+    collector::CharCollector,
+    font::FontCache,
+    page::PageRenderer,
+    table::{Orientation, Ruling},
+};
 
 /// Box in PDF coorinates
 ///
@@ -43,6 +50,23 @@ impl CropBox {
     fn is_inside(&self, x: f32, y: f32) -> bool {
         self.left <= x && self.right >= x && self.bottom <= y && self.top >= y
     }
+
+    /// The part of the ruling that is inside the box, if any
+    fn clip_ruling(&self, ruling: &Ruling) -> Option<Ruling> {
+        let ((position_min, position_max), (min, max)) = match ruling.orientation {
+            Orientation::Horizontal => ((self.bottom, self.top), (self.left, self.right)),
+            Orientation::Vertical => ((self.left, self.right), (self.bottom, self.top)),
+        };
+        if ruling.position < position_min || ruling.position > position_max {
+            return None;
+        }
+        let start = ruling.start.max(min);
+        let end = ruling.end.min(max);
+        if start >= end {
+            return None;
+        }
+        Some(Ruling::new(ruling.orientation, ruling.position, start, end))
+    }
 }
 impl Default for CropBox {
     fn default() -> Self {
@@ -55,7 +79,7 @@ impl Default for CropBox {
     }
 }
 
-pub use page_of_lines::PageOfLines;
+pub use page_of_lines::{PageOfLines, PageTable};
 
 pub fn parse_pdf(buffer: &[u8], crop: CropBox) -> Result<Vec<PageOfLines>> {
     let pdf_file = pdf::file::FileOptions::uncached().load(buffer)?;
@@ -69,3 +93,44 @@ pub fn parse_pdf(buffer: &[u8], crop: CropBox) -> Result<Vec<PageOfLines>> {
     }
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_clip_ruling() {
+        let crop = CropBox {
+            bottom: 50.0,
+            left: 40.0,
+            top: 800.0,
+            right: 550.0,
+        };
+        let cases = [
+            (
+                Ruling::new(Orientation::Horizontal, 100.0, 60.0, 500.0),
+                Some(Ruling::new(Orientation::Horizontal, 100.0, 60.0, 500.0)),
+            ),
+            (
+                Ruling::new(Orientation::Horizontal, 100.0, 0.0, 595.0),
+                Some(Ruling::new(Orientation::Horizontal, 100.0, 40.0, 550.0)),
+            ),
+            // Header rule
+            (
+                Ruling::new(Orientation::Horizontal, 810.0, 40.0, 550.0),
+                None,
+            ),
+            (
+                Ruling::new(Orientation::Vertical, 300.0, 20.0, 820.0),
+                Some(Ruling::new(Orientation::Vertical, 300.0, 50.0, 800.0)),
+            ),
+            (Ruling::new(Orientation::Vertical, 300.0, 0.0, 40.0), None),
+            (Ruling::new(Orientation::Vertical, 20.0, 100.0, 200.0), None),
+        ];
+        for (ruling, expected) in cases {
+            assert_eq!(crop.clip_ruling(&ruling), expected, "{ruling:?}");
+        }
+    }
+}
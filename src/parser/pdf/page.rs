@@ -28,6 +28,7 @@ use pdf::{
 use super::{
     collector::CharCollector,
     font::{FastFont, FontCache},
+    path::{PagePoint, PathBuilder},
     textstate::TextState,
 };
 use crate::parser::pdf::actual_text::ActualTextCollector;
@@ -49,6 +50,8 @@ where
     marked_content_stack: Vec<MarkedContentType>,
 
     actual_text_collector: Option<ActualTextCollector>,
+
+    path: PathBuilder,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -77,6 +80,7 @@ where
             state_stack: Vec::new(),
             marked_content_stack: Vec::new(),
             actual_text_collector: None,
+            path: PathBuilder::default(),
         })
     }
 
@@ -138,6 +142,11 @@ where
         Ok(())
     }
 
+    /// Convert a point in user space to page coordinates
+    fn page_point(&self, x: f32, y: f32) -> PagePoint {
+        self.state.ctm.transform_point(PagePoint::new(x, y))
+    }
+
     fn handle_xobject(&mut self, name: Name) -> Result<()> {
         let resources = self.resources()?;
         let xobject_ref = resources
@@ -275,6 +284,25 @@ where
                 }
             }
 
+            // --- Paths ---
+            Op::Transform { matrix } => {
+                self.state.ctm = Transform2D::from(matrix).then(&self.state.ctm);
+            }
+            Op::MoveTo { p } => self.path.move_to(self.page_point(p.x, p.y)),
+            Op::LineTo { p } => self.path.line_to(self.page_point(p.x, p.y)),
+            Op::CurveTo { p, .. } => self.path.curve_to(self.page_point(p.x, p.y)),
+            Op::Rect { rect } => self.path.rect(
+                self.page_point(rect.x, rect.y),
+                self.page_point(rect.x + rect.width, rect.y + rect.height),
+            ),
+            Op::Close => self.path.close(),
+            Op::Stroke | Op::Fill { .. } | Op::FillAndStroke { .. } => {
+                self.collector.render_rulings(self.path.take());
+            }
+            Op::EndPath => {
+                self.path.take();
+            }
+
             // --- Xobject ---
             Op::XObject { name } => {
                 self.handle_xobject(name)?;
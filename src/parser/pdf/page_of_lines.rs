@@ -19,11 +19,15 @@ use serde::Serialize;
 
 use super::{
     collector::{CharCollector, PositionedChar},
+    table::detect_tables,
     util::compare_float_for_sorting,
 };
-use crate::util::indentedline::{IndentedLine, IndentedLinePart, EMPTY_LINE};
+use crate::{
+    structure::Table,
+    util::indentedline::{IndentedLine, IndentedLinePart, EMPTY_LINE},
+};
 
-const SAME_LINE_EPSILON: f32 = 0.5;
+pub const SAME_LINE_EPSILON: f32 = 0.5;
 const ADDITIONAL_EMPTY_LINE_THRESHOLD: f32 = 16.0;
 const SPACE_DETECTION_THRESHOLD_RATIO: f32 = 0.5;
 const JUSTIFIED_DETECTION_THRESHOLD_RATIO: f32 = 0.8;
@@ -31,6 +35,21 @@ const JUSTIFIED_DETECTION_THRESHOLD_RATIO: f32 = 0.8;
 #[derive(Debug, Serialize)]
 pub struct PageOfLines {
     pub lines: Vec<IndentedLine>,
+    /// The largest font size in each of the lines. 0.0 for empty lines.
+    #[serde(skip)]
+    pub font_sizes: Vec<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<PageTable>,
+}
+
+/// A table on the page. Its text is also present in `lines`.
+#[derive(Debug, Serialize)]
+pub struct PageTable {
+    /// Index of the first line in the table
+    pub first_line: usize,
+    /// Index of the last line in the table (inclusive)
+    pub last_line: usize,
+    pub table: Table,
 }
 
 impl TryFrom<CharCollector> for PageOfLines {
@@ -41,7 +60,11 @@ impl TryFrom<CharCollector> for PageOfLines {
             .chars
             .iter()
             .fold(0.0_f32, |acc, c| acc.max(c.x + c.width));
+        let detected_tables = detect_tables(&value.rulings, &value.chars);
         let mut result = Vec::<IndentedLine>::new();
+        let mut font_sizes = Vec::<f32>::new();
+        // The y coordinate of every non-empty line in result
+        let mut line_positions = Vec::<(usize, f32)>::new();
         let mut chars = value.chars;
         chars.sort_unstable_by(|c1, c2| compare_float_for_sorting(c2.y, c1.y));
         let mut current_line = Vec::<PositionedChar>::new();
@@ -57,21 +80,55 @@ impl TryFrom<CharCollector> for PageOfLines {
             if y_diff < SAME_LINE_EPSILON {
                 current_line.push(current_char);
             } else {
+                line_positions.push((result.len(), current_line[0].y));
+                font_sizes.push(largest_font_size(&current_line));
                 result.push(consolidate_line(current_line, estimated_right_margin));
                 // Add empty line on a "big-enough gap"
                 // Should be based on actual font height, but this is
                 // good enough for the rest of the parsing steps.
                 if y_diff > ADDITIONAL_EMPTY_LINE_THRESHOLD {
+                    font_sizes.push(0.0);
                     result.push(EMPTY_LINE);
                 }
                 current_line = vec![current_char];
             }
         }
+        if let Some(first_char) = current_line.first() {
+            line_positions.push((result.len(), first_char.y));
+        }
+        font_sizes.push(largest_font_size(&current_line));
         result.push(consolidate_line(current_line, estimated_right_margin));
-        Ok(PageOfLines { lines: result })
+        let tables = detected_tables
+            .into_iter()
+            .filter_map(|detected| {
+                let mut lines_in_table = line_positions
+                    .iter()
+                    .filter(|(_, y)| *y < detected.top && *y > detected.bottom)
+                    .map(|(index, _)| *index);
+                let first_line = lines_in_table.next()?;
+                let last_line = lines_in_table.next_back().unwrap_or(first_line);
+                Some(PageTable {
+                    first_line,
+                    last_line,
+                    table: detected.table,
+                })
+            })
+            .collect();
+        Ok(PageOfLines {
+            lines: result,
+            font_sizes,
+            tables,
+        })
     }
 }
-fn consolidate_line(mut chars: Vec<PositionedChar>, estimated_right_margin: f32) -> IndentedLine {
+fn largest_font_size(chars: &[PositionedChar]) -> f32 {
+    chars.iter().fold(0.0_f32, |acc, c| acc.max(c.font_size))
+}
+
+pub fn consolidate_line(
+    mut chars: Vec<PositionedChar>,
+    estimated_right_margin: f32,
+) -> IndentedLine {
     chars.sort_unstable_by(|c1, c2| compare_float_for_sorting(c1.x, c2.x));
     let last_char = match chars.last() {
         Some(x) => x,
@@ -0,0 +1,221 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use euclid::Point2D;
+use pdf::content::PdfSpace;
+
+use super::table::{Orientation, Ruling};
+
+/// Lines that are not perfectly axis aligned are still accepted up to this difference
+const AXIS_ALIGNED_EPSILON: f32 = 0.5;
+/// Rectangles thinner than this are drawn lines, not boxes
+const THIN_RECT_THRESHOLD: f32 = 2.0;
+
+pub type PagePoint = Point2D<f32, PdfSpace>;
+
+/// Collects the horizontal and vertical lines of the path currently under construction.
+///
+/// Curves and slanted lines are not interesting for table detection, and are ignored.
+#[derive(Debug, Default)]
+pub struct PathBuilder {
+    rulings: Vec<Ruling>,
+    current_point: Option<PagePoint>,
+    subpath_start: Option<PagePoint>,
+}
+
+impl PathBuilder {
+    pub fn move_to(&mut self, p: PagePoint) {
+        self.current_point = Some(p);
+        self.subpath_start = Some(p);
+    }
+
+    pub fn line_to(&mut self, p: PagePoint) {
+        if let Some(current_point) = self.current_point {
+            self.add_line(current_point, p);
+        }
+        self.current_point = Some(p);
+    }
+
+    pub fn curve_to(&mut self, p: PagePoint) {
+        self.current_point = Some(p);
+    }
+
+    pub fn rect(&mut self, corner: PagePoint, opposite_corner: PagePoint) {
+        let (x0, x1) = min_max(corner.x, opposite_corner.x);
+        let (y0, y1) = min_max(corner.y, opposite_corner.y);
+        let thin_horizontally = x1 - x0 <= THIN_RECT_THRESHOLD;
+        let thin_vertically = y1 - y0 <= THIN_RECT_THRESHOLD;
+        match (thin_horizontally, thin_vertically) {
+            (true, true) => (),
+            (false, true) => self.rulings.push(Ruling::new(
+                Orientation::Horizontal,
+                (y0 + y1) / 2.0,
+                x0,
+                x1,
+            )),
+            (true, false) => {
+                self.rulings
+                    .push(Ruling::new(Orientation::Vertical, (x0 + x1) / 2.0, y0, y1))
+            }
+            (false, false) => {
+                let corners = [
+                    PagePoint::new(x0, y0),
+                    PagePoint::new(x1, y0),
+                    PagePoint::new(x1, y1),
+                    PagePoint::new(x0, y1),
+                ];
+                for i in 0..4 {
+                    self.add_line(corners[i], corners[(i + 1) % 4]);
+                }
+            }
+        }
+        self.move_to(corner);
+    }
+
+    pub fn close(&mut self) {
+        if let (Some(current_point), Some(subpath_start)) = (self.current_point, self.subpath_start)
+        {
+            self.add_line(current_point, subpath_start);
+        }
+        self.current_point = self.subpath_start;
+    }
+
+    /// End the path, and return the collected lines
+    pub fn take(&mut self) -> Vec<Ruling> {
+        self.current_point = None;
+        self.subpath_start = None;
+        std::mem::take(&mut self.rulings)
+    }
+
+    fn add_line(&mut self, p1: PagePoint, p2: PagePoint) {
+        let dx = (p1.x - p2.x).abs();
+        let dy = (p1.y - p2.y).abs();
+        if dy < AXIS_ALIGNED_EPSILON && dx > dy {
+            self.rulings.push(Ruling::new(
+                Orientation::Horizontal,
+                (p1.y + p2.y) / 2.0,
+                p1.x,
+                p2.x,
+            ));
+        } else if dx < AXIS_ALIGNED_EPSILON && dy > dx {
+            self.rulings.push(Ruling::new(
+                Orientation::Vertical,
+                (p1.x + p2.x) / 2.0,
+                p1.y,
+                p2.y,
+            ));
+        }
+    }
+}
+
+fn min_max(a: f32, b: f32) -> (f32, f32) {
+    (a.min(b), a.max(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn p(x: f32, y: f32) -> PagePoint {
+        PagePoint::new(x, y)
+    }
+
+    #[test]
+    fn test_thin_rects() {
+        let mut builder = PathBuilder::default();
+        builder.rect(p(10.0, 100.0), p(200.0, 101.0));
+        builder.rect(p(50.5, 300.0), p(50.0, 120.0));
+        builder.rect(p(0.0, 0.0), p(1.0, 1.0));
+        assert_eq!(
+            builder.take(),
+            vec![
+                Ruling::new(Orientation::Horizontal, 100.5, 10.0, 200.0),
+                Ruling::new(Orientation::Vertical, 50.25, 120.0, 300.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_thick_rect() {
+        let mut builder = PathBuilder::default();
+        builder.rect(p(10.0, 100.0), p(200.0, 50.0));
+        assert_eq!(
+            builder.take(),
+            vec![
+                Ruling::new(Orientation::Horizontal, 50.0, 10.0, 200.0),
+                Ruling::new(Orientation::Vertical, 200.0, 50.0, 100.0),
+                Ruling::new(Orientation::Horizontal, 100.0, 10.0, 200.0),
+                Ruling::new(Orientation::Vertical, 10.0, 50.0, 100.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_close() {
+        let mut builder = PathBuilder::default();
+        builder.move_to(p(10.0, 10.0));
+        builder.line_to(p(100.0, 10.0));
+        builder.line_to(p(100.0, 50.0));
+        builder.close();
+        // The closing line is slanted, so only the first two are kept
+        builder.move_to(p(200.0, 10.0));
+        builder.line_to(p(300.0, 10.0));
+        builder.line_to(p(300.0, 50.0));
+        builder.line_to(p(200.0, 50.0));
+        builder.close();
+        assert_eq!(
+            builder.take(),
+            vec![
+                Ruling::new(Orientation::Horizontal, 10.0, 10.0, 100.0),
+                Ruling::new(Orientation::Vertical, 100.0, 10.0, 50.0),
+                Ruling::new(Orientation::Horizontal, 10.0, 200.0, 300.0),
+                Ruling::new(Orientation::Vertical, 300.0, 10.0, 50.0),
+                Ruling::new(Orientation::Horizontal, 50.0, 200.0, 300.0),
+                Ruling::new(Orientation::Vertical, 200.0, 10.0, 50.0),
+            ]
+        );
+        assert_eq!(builder.take(), vec![]);
+    }
+
+    #[test]
+    fn test_slanted_lines_and_curves() {
+        let mut builder = PathBuilder::default();
+        builder.move_to(p(10.0, 10.0));
+        builder.line_to(p(100.0, 20.0));
+        builder.curve_to(p(150.0, 20.0));
+        builder.line_to(p(250.0, 20.25));
+        builder.line_to(p(250.25, 120.0));
+        // No current point after take
+        builder.take();
+        builder.line_to(p(300.0, 120.0));
+        assert_eq!(builder.take(), vec![]);
+
+        builder.move_to(p(10.0, 10.0));
+        builder.line_to(p(100.0, 20.0));
+        builder.curve_to(p(150.0, 20.0));
+        builder.line_to(p(250.0, 20.25));
+        builder.line_to(p(250.25, 120.0));
+        assert_eq!(
+            builder.take(),
+            vec![
+                Ruling::new(Orientation::Horizontal, 20.125, 150.0, 250.0),
+                Ruling::new(Orientation::Vertical, 250.125, 20.25, 120.0),
+            ]
+        );
+    }
+}
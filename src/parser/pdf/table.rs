@@ -0,0 +1,389 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::ops::Range;
+
+use super::{
+    collector::PositionedChar,
+    page_of_lines::{consolidate_line, SAME_LINE_EPSILON},
+    util::compare_float_for_sorting,
+};
+use crate::{
+    structure::{Table, TableCell, TableRow},
+    util::indentedline::IndentedLine,
+};
+
+/// Rule lines closer than this are considered to be the same line
+const RULING_EPSILON: f32 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+/// A horizontal or vertical line drawn on the page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ruling {
+    pub orientation: Orientation,
+    /// y for horizontal, x for vertical rulings
+    pub position: f32,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Ruling {
+    pub fn new(orientation: Orientation, position: f32, start: f32, end: f32) -> Self {
+        Self {
+            orientation,
+            position,
+            start: start.min(end),
+            end: start.max(end),
+        }
+    }
+
+    fn covers(&self, coordinate: f32) -> bool {
+        self.start - RULING_EPSILON <= coordinate && self.end + RULING_EPSILON >= coordinate
+    }
+
+    fn intersects(&self, other: &Ruling) -> bool {
+        self.orientation != other.orientation
+            && self.covers(other.position)
+            && other.covers(self.position)
+    }
+
+    fn is_at(&self, orientation: Orientation, position: f32) -> bool {
+        self.orientation == orientation && (self.position - position).abs() < RULING_EPSILON
+    }
+}
+
+/// The grid of a table, as defined by the rulings. Row boundaries go from top to bottom.
+#[derive(Debug, Clone)]
+struct TableGrid {
+    xs: Vec<f32>,
+    ys: Vec<f32>,
+    rulings: Vec<Ruling>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DetectedTable {
+    pub table: Table,
+    /// Top and bottom of the table in PDF coordinates
+    pub top: f32,
+    pub bottom: f32,
+}
+
+/// Find the tables on a page, and fill them with the characters inside them.
+///
+/// Only tables with both horizontal and vertical rulings are detected,
+/// and they need to have at least two cells.
+pub fn detect_tables(rulings: &[Ruling], chars: &[PositionedChar]) -> Vec<DetectedTable> {
+    connected_rulings(rulings)
+        .into_iter()
+        .filter_map(TableGrid::from_rulings)
+        .filter_map(|grid| grid.fill(chars))
+        .collect()
+}
+
+/// Group the rulings into sets of lines that touch each other
+fn connected_rulings(rulings: &[Ruling]) -> Vec<Vec<Ruling>> {
+    let mut group_of: Vec<usize> = (0..rulings.len()).collect();
+    fn root(group_of: &mut [usize], mut i: usize) -> usize {
+        while group_of[i] != i {
+            group_of[i] = group_of[group_of[i]];
+            i = group_of[i];
+        }
+        i
+    }
+    for i in 0..rulings.len() {
+        for j in i + 1..rulings.len() {
+            if rulings[i].intersects(&rulings[j]) {
+                let (root_i, root_j) = (root(&mut group_of, i), root(&mut group_of, j));
+                group_of[root_j] = root_i;
+            }
+        }
+    }
+    let mut groups: Vec<(usize, Vec<Ruling>)> = Vec::new();
+    for (i, ruling) in rulings.iter().enumerate() {
+        let group = root(&mut group_of, i);
+        match groups.iter_mut().find(|(g, _)| *g == group) {
+            Some((_, group_rulings)) => group_rulings.push(*ruling),
+            None => groups.push((group, vec![*ruling])),
+        }
+    }
+    groups.into_iter().map(|(_, g)| g).collect()
+}
+
+/// Sorted positions of the rulings, with close ones merged
+fn distinct_positions(rulings: &[Ruling], orientation: Orientation) -> Vec<f32> {
+    let mut positions: Vec<f32> = rulings
+        .iter()
+        .filter(|r| r.orientation == orientation)
+        .map(|r| r.position)
+        .collect();
+    positions.sort_unstable_by(|p1, p2| compare_float_for_sorting(*p1, *p2));
+    positions.dedup_by(|p2, p1| (*p2 - *p1).abs() < RULING_EPSILON);
+    positions
+}
+
+impl TableGrid {
+    fn from_rulings(rulings: Vec<Ruling>) -> Option<Self> {
+        let xs = distinct_positions(&rulings, Orientation::Vertical);
+        let mut ys = distinct_positions(&rulings, Orientation::Horizontal);
+        ys.reverse();
+        if xs.len() < 2 || ys.len() < 2 {
+            return None;
+        }
+        Some(Self { xs, ys, rulings })
+    }
+
+    fn has_separator(&self, orientation: Orientation, position: f32, at: f32) -> bool {
+        self.rulings
+            .iter()
+            .any(|r| r.is_at(orientation, position) && r.covers(at))
+    }
+
+    /// Is there a vertical line between column `col - 1` and `col` in row `row`
+    fn has_vertical_separator(&self, col: usize, row: usize) -> bool {
+        let row_middle = (self.ys[row] + self.ys[row + 1]) / 2.0;
+        self.has_separator(Orientation::Vertical, self.xs[col], row_middle)
+    }
+
+    /// Is there a horizontal line between row `row - 1` and `row` in column `col`
+    fn has_horizontal_separator(&self, row: usize, col: usize) -> bool {
+        let col_middle = (self.xs[col] + self.xs[col + 1]) / 2.0;
+        self.has_separator(Orientation::Horizontal, self.ys[row], col_middle)
+    }
+
+    /// Calculate the cells with the spans, and fill them with text
+    fn fill(&self, chars: &[PositionedChar]) -> Option<DetectedTable> {
+        let row_count = self.ys.len() - 1;
+        let col_count = self.xs.len() - 1;
+        let mut occupied = vec![vec![false; col_count]; row_count];
+        let mut rows = Vec::with_capacity(row_count);
+        let mut cell_count = 0;
+        for row in 0..row_count {
+            let mut cells = Vec::new();
+            for col in 0..col_count {
+                if occupied[row][col] {
+                    continue;
+                }
+                let mut colspan = 1;
+                while col + colspan < col_count && !self.has_vertical_separator(col + colspan, row)
+                {
+                    colspan += 1;
+                }
+                let mut rowspan = 1;
+                while row + rowspan < row_count
+                    && (col..col + colspan)
+                        .all(|c| !self.has_horizontal_separator(row + rowspan, c))
+                {
+                    rowspan += 1;
+                }
+                for occupied_row in &mut occupied[row..row + rowspan] {
+                    for occupied_cell in &mut occupied_row[col..col + colspan] {
+                        *occupied_cell = true;
+                    }
+                }
+                cells.push(TableCell {
+                    text: cell_text(
+                        chars,
+                        self.xs[col]..self.xs[col + colspan],
+                        self.ys[row + rowspan]..self.ys[row],
+                    ),
+                    colspan,
+                    rowspan,
+                });
+                cell_count += 1;
+            }
+            rows.push(TableRow { cells });
+        }
+        if cell_count < 2 {
+            return None;
+        }
+        Some(DetectedTable {
+            table: Table { rows },
+            top: self.ys[0],
+            bottom: self.ys[row_count],
+        })
+    }
+}
+
+fn cell_text(chars: &[PositionedChar], x_range: Range<f32>, y_range: Range<f32>) -> String {
+    let mut cell_chars: Vec<PositionedChar> = chars
+        .iter()
+        .filter(|c| x_range.contains(&(c.x + c.width / 2.0)) && y_range.contains(&c.y))
+        .cloned()
+        .collect();
+    cell_chars.sort_unstable_by(|c1, c2| compare_float_for_sorting(c2.y, c1.y));
+    let mut lines: Vec<IndentedLine> = Vec::new();
+    let mut current_line: Vec<PositionedChar> = Vec::new();
+    for c in cell_chars {
+        if let Some(first) = current_line.first() {
+            if (first.y - c.y).abs() >= SAME_LINE_EPSILON {
+                lines.push(consolidate_line(
+                    std::mem::take(&mut current_line),
+                    x_range.end,
+                ));
+            }
+        }
+        current_line.push(c);
+    }
+    lines.push(consolidate_line(current_line, x_range.end));
+    IndentedLine::join(&lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn h(y: f32, x1: f32, x2: f32) -> Ruling {
+        Ruling::new(Orientation::Horizontal, y, x1, x2)
+    }
+
+    fn v(x: f32, y1: f32, y2: f32) -> Ruling {
+        Ruling::new(Orientation::Vertical, x, y1, y2)
+    }
+
+    fn text_at(x: f32, y: f32, text: &str) -> Vec<PositionedChar> {
+        text.chars()
+            .enumerate()
+            .filter(|(_, c)| *c != ' ')
+            .map(|(i, content)| PositionedChar {
+                x: x + i as f32 * 5.0,
+                y,
+                width: 4.0,
+                width_of_space: 4.0,
+                bold: false,
+                font_size: 9.0,
+                content,
+            })
+            .collect()
+    }
+
+    fn cell(text: &str, colspan: usize, rowspan: usize) -> TableCell {
+        TableCell {
+            text: text.to_owned(),
+            colspan,
+            rowspan,
+        }
+    }
+
+    #[test]
+    fn test_simple_table() {
+        // +-----+-----+
+        // | A   | B   |
+        // +-----+-----+
+        // | C   | D E |
+        // +-----+-----+
+        let rulings = [
+            h(100.0, 0.0, 200.0),
+            h(80.0, 0.0, 200.0),
+            h(60.0, 0.0, 200.0),
+            v(0.0, 60.0, 100.0),
+            v(100.0, 60.0, 100.0),
+            v(200.0, 60.0, 100.0),
+            // Some unrelated line somewhere else
+            h(10.0, 0.0, 200.0),
+        ];
+        let chars = [
+            text_at(5.0, 85.0, "A"),
+            text_at(105.0, 85.0, "B"),
+            text_at(5.0, 65.0, "C"),
+            text_at(105.0, 65.0, "D E"),
+            text_at(5.0, 20.0, "Outside"),
+        ]
+        .concat();
+        let tables = detect_tables(&rulings, &chars);
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].top, 100.0);
+        assert_eq!(tables[0].bottom, 60.0);
+        assert_eq!(
+            tables[0].table,
+            Table {
+                rows: vec![
+                    TableRow {
+                        cells: vec![cell("A", 1, 1), cell("B", 1, 1)]
+                    },
+                    TableRow {
+                        cells: vec![cell("C", 1, 1), cell("D E", 1, 1)]
+                    },
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_spans() {
+        // +-----------+
+        // | Header    |
+        // +-----+-----+
+        // | A   | B   |
+        // |     +-----+
+        // |     | C   |
+        // +-----+-----+
+        let rulings = [
+            h(100.0, 0.0, 200.0),
+            h(80.0, 0.0, 200.0),
+            h(60.0, 100.0, 200.0),
+            h(40.0, 0.0, 200.0),
+            v(0.0, 40.0, 100.0),
+            v(100.0, 40.0, 80.0),
+            v(200.0, 40.0, 100.0),
+        ];
+        let chars = [
+            text_at(5.0, 85.0, "Header"),
+            text_at(5.0, 65.0, "A"),
+            text_at(105.0, 65.0, "B"),
+            text_at(105.0, 45.0, "C"),
+        ]
+        .concat();
+        let tables = detect_tables(&rulings, &chars);
+        assert_eq!(tables.len(), 1);
+        assert_eq!(
+            tables[0].table,
+            Table {
+                rows: vec![
+                    TableRow {
+                        cells: vec![cell("Header", 2, 1)]
+                    },
+                    TableRow {
+                        cells: vec![cell("A", 1, 2), cell("B", 1, 1)]
+                    },
+                    TableRow {
+                        cells: vec![cell("C", 1, 1)]
+                    },
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_not_a_table() {
+        // Underlines and a single box are not tables
+        let rulings = [
+            h(100.0, 0.0, 200.0),
+            h(80.0, 0.0, 200.0),
+            h(50.0, 0.0, 100.0),
+            h(30.0, 0.0, 100.0),
+            v(0.0, 30.0, 50.0),
+            v(100.0, 30.0, 50.0),
+        ];
+        assert!(detect_tables(&rulings, &text_at(5.0, 40.0, "Box")).is_empty());
+    }
+}
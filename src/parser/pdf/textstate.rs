@@ -23,6 +23,9 @@ use super::font::FastFont;
 
 #[derive(Clone, Debug)]
 pub struct TextState {
+    /// Current transformation matrix. Applied to both text and paths, so that
+    /// characters and rulings end up in the same coordinate space.
+    pub ctm: Transform2D<f32, PdfSpace, PdfSpace>,
     pub text_matrix: Transform2D<f32, PdfSpace, PdfSpace>,
     pub line_matrix: Transform2D<f32, PdfSpace, PdfSpace>,
     pub char_spacing: f32,
@@ -37,6 +40,7 @@ pub struct TextState {
 impl Default for TextState {
     fn default() -> Self {
         Self {
+            ctm: Transform2D::identity(),
             text_matrix: Transform2D::identity(),
             line_matrix: Transform2D::identity(),
             char_spacing: 0.0,
@@ -79,7 +83,6 @@ impl TextState {
             0.0,
             self.rise,
         );
-        /* TODO: .then(CTM) */
-        rendering_pre_matrix.then(&self.text_matrix)
+        rendering_pre_matrix.then(&self.text_matrix).then(&self.ctm)
     }
 }
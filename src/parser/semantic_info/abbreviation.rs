@@ -112,6 +112,7 @@ mod tests {
             ActIdentifier {
                 year: 2012,
                 number: 13,
+                ..Default::default()
             },
         );
         cache.add(
@@ -119,6 +120,7 @@ mod tests {
             ActIdentifier {
                 year: 2012,
                 number: 14,
+                ..Default::default()
             },
         );
         cache.add(
@@ -126,6 +128,7 @@ mod tests {
             ActIdentifier {
                 year: 2019,
                 number: 19,
+                ..Default::default()
             },
         );
         assert!(cache.has_changed());
@@ -135,7 +138,8 @@ mod tests {
             data.get("Tv."),
             Some(&ActIdentifier {
                 year: 2012,
-                number: 14
+                number: 14,
+                ..Default::default()
             })
         );
 
@@ -146,6 +150,7 @@ mod tests {
             ActIdentifier {
                 year: 2012,
                 number: 14,
+                ..Default::default()
             },
         );
         assert!(!new_cache.has_changed());
@@ -154,6 +159,7 @@ mod tests {
             ActIdentifier {
                 year: 2017,
                 number: 17,
+                ..Default::default()
             },
         );
         assert!(new_cache.has_changed());
@@ -165,6 +171,7 @@ mod tests {
             ActIdentifier {
                 year: 2042,
                 number: 42,
+                ..Default::default()
             },
         );
         assert!(new_cache2.has_changed());
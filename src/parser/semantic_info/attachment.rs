@@ -0,0 +1,99 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{anyhow, Result};
+use hun_law_grammar::*;
+
+use super::{abbreviation::AbbreviationCache, reference::convert_act_reference};
+use crate::{
+    identifier::{
+        range::{IdentifierRange, IdentifierRangeFrom},
+        NumericIdentifier,
+    },
+    reference::attachment::AttachmentReference as AttachmentReferenceInfo,
+    semantic_info::OutgoingAttachmentReference,
+};
+
+/// Collect the attachment references of a simple list of expressions.
+///
+/// An attachment reference directly after an act reference (e.g.
+/// "a 2012. évi I. törvény 1. melléklete") refers to the attachment of that act.
+pub fn get_outgoing_attachment_references(
+    abbreviation_cache: &AbbreviationCache,
+    root: &Root,
+) -> Result<Vec<OutgoingAttachmentReference>> {
+    let mut result = Vec::new();
+    if let Root_content::ListOfSimpleExpressions(list) = &root.content {
+        let mut act = None;
+        for item in &list.contents {
+            match item {
+                AnySimpleExpression::CompoundReference(compound_reference) => {
+                    act = match &compound_reference.act_reference {
+                        // TODO: Errors are swallowed here, same as for normal references.
+                        Some(act_reference) if compound_reference.references.is_empty() => {
+                            convert_act_reference(abbreviation_cache, act_reference).ok()
+                        }
+                        _ => None,
+                    };
+                }
+                AnySimpleExpression::AttachmentReference(attachment_reference) => {
+                    for part in &attachment_reference.attachment {
+                        result.push(OutgoingAttachmentReference {
+                            start: part.position.start,
+                            end: part.position.end,
+                            reference: AttachmentReferenceInfo {
+                                act,
+                                attachment: part.try_into()?,
+                            },
+                        });
+                    }
+                    act = None;
+                }
+                _ => act = None,
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Convert an attachment reference that always refers to the current act
+pub fn convert_attachment_reference(
+    elem: &AttachmentReference,
+) -> Result<Vec<AttachmentReferenceInfo>> {
+    elem.attachment
+        .iter()
+        .map(|part| {
+            Ok(AttachmentReferenceInfo {
+                act: None,
+                attachment: part.try_into()?,
+            })
+        })
+        .collect()
+}
+
+impl TryFrom<&AttachmentReferencePart> for IdentifierRange<NumericIdentifier> {
+    type Error = anyhow::Error;
+
+    fn try_from(element: &AttachmentReferencePart) -> Result<Self, Self::Error> {
+        if let Some(id) = &element.id {
+            Ok(Self::from_single(id.parse()?))
+        } else if let (Some(start), Some(end)) = (&element.start, &element.end) {
+            Ok(Self::from_range(start.parse()?, end.parse()?))
+        } else {
+            Err(anyhow!("Grammar somehow produced an invalid combination"))
+        }
+    }
+}
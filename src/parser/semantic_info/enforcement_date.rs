@@ -20,6 +20,7 @@ use hun_law_grammar::*;
 
 use super::{
     abbreviation::AbbreviationCache,
+    attachment::convert_attachment_reference,
     reference::{FeedReferenceBuilder, OutgoingReferenceBuilder},
 };
 use crate::{
@@ -34,6 +35,7 @@ pub fn convert_enforcement_date(
 ) -> Result<semantic_info::EnforcementDate> {
     let mut ref_builder = OutgoingReferenceBuilder::new(abbreviation_cache);
     let mut structural_positions = Vec::new();
+    let mut attachment_positions = Vec::new();
     for ed_reference in &elem.references {
         match ed_reference {
             EnforcementDateReference::AnyStructuralReference(asr) => {
@@ -50,7 +52,9 @@ pub fn convert_enforcement_date(
                     })
                 }
             }
-            EnforcementDateReference::AttachmentReference(_) => {}
+            EnforcementDateReference::AttachmentReference(ar) => {
+                attachment_positions.extend(convert_attachment_reference(ar)?)
+            }
             EnforcementDateReference::Reference(r) => ref_builder.feed(r)?,
         }
     }
@@ -69,6 +73,7 @@ pub fn convert_enforcement_date(
     Ok(semantic_info::EnforcementDate {
         positions,
         structural_positions,
+        attachment_positions,
         is_default: elem.references.is_empty(),
         date,
         inline_repeal,
@@ -23,6 +23,7 @@ use crate::{
 };
 
 pub mod abbreviation;
+pub mod attachment;
 pub mod block_amendment;
 pub mod enforcement_date;
 pub mod reference;
@@ -92,8 +93,10 @@ mod tests {
             identifier: ActIdentifier {
                 year: 2345,
                 number: 0xd,
+                ..Default::default()
             },
             publication_date: NaiveDate::from_ymd(2345, 6, 7),
+            adoption_date: None,
             subject: "A tesztelésről".into(),
             preamble: "A tesztelés nagyon fontos, és egyben kötelező".into(),
             contained_abbreviations: Default::default(),
@@ -118,6 +121,9 @@ mod tests {
                 last_change: None,
             }
             .into()],
+            signatories: Vec::new(),
+            attachments: Vec::new(),
+            footnotes: Vec::new(),
         };
         let abbrevs_changed = test_act.add_semantic_info().unwrap();
         let expected_abbreviations = [(
@@ -125,6 +131,7 @@ mod tests {
             ActIdentifier {
                 year: 2022,
                 number: 22,
+                ..Default::default()
             },
         )]
         .into();
@@ -140,6 +147,7 @@ mod tests {
             ActIdentifier {
                 year: 2022,
                 number: 22,
+                ..Default::default()
             }
         );
 
@@ -162,6 +170,7 @@ mod tests {
             ActIdentifier {
                 year: 2022,
                 number: 22,
+                ..Default::default()
             }
         );
 
@@ -178,6 +187,7 @@ mod tests {
             ActIdentifier {
                 year: 2033,
                 number: 33,
+                ..Default::default()
             },
         )]
         .into();
@@ -193,6 +203,7 @@ mod tests {
             ActIdentifier {
                 year: 2033,
                 number: 33,
+                ..Default::default()
             }
         );
     }
@@ -203,8 +214,10 @@ mod tests {
             identifier: ActIdentifier {
                 year: 2345,
                 number: 0xd,
+                ..Default::default()
             },
             publication_date: NaiveDate::from_ymd(2345, 6, 7),
+            adoption_date: None,
             subject: "A tesztelésről".into(),
             preamble: "A tesztelés nagyon fontos, és egyben kötelező".into(),
             contained_abbreviations: Default::default(),
@@ -240,6 +253,9 @@ mod tests {
                 }
                 .into(),
             ],
+            signatories: Vec::new(),
+            attachments: Vec::new(),
+            footnotes: Vec::new(),
         };
         test_act.add_semantic_info().unwrap();
         assert_eq!(
@@ -21,7 +21,7 @@ use super::abbreviation::AbbreviationCache;
 use crate::{
     identifier::{
         range::IdentifierRangeFrom, ActIdentifier, AlphabeticIdentifier, ArticleIdentifier,
-        NumericIdentifier, PrefixedAlphabeticIdentifier,
+        DocumentKind, NumericIdentifier, PrefixedAlphabeticIdentifier,
     },
     reference::{
         builder::{ReferenceBuilder, ReferenceBuilderSetPart},
@@ -478,12 +478,40 @@ impl TryFrom<&ActId> for ActIdentifier {
     type Error = anyhow::Error;
 
     fn try_from(act_id: &ActId) -> Result<Self, Self::Error> {
-        Ok(ActIdentifier {
-            year: act_id.year.parse()?,
-            number: roman::from(&act_id.number).ok_or_else(|| {
-                anyhow!("{} is not a valid suffixed roman numeral", act_id.number)
-            })?,
-        })
+        let year = act_id.year.parse()?;
+        if let Some(number) = &act_id.number {
+            return Ok(ActIdentifier {
+                year,
+                number: roman::from(number)
+                    .ok_or_else(|| anyhow!("{} is not a valid suffixed roman numeral", number))?,
+                kind: DocumentKind::Act,
+            });
+        }
+        match (
+            &act_id.decree_number,
+            &act_id.month,
+            &act_id.day,
+            &act_id.issuer,
+            &act_id.decree_type,
+        ) {
+            (Some(number), Some(month), Some(day), Some(issuer), Some(decree_type)) => {
+                let month = roman::from(month)
+                    .and_then(|m| m.try_into().ok())
+                    .ok_or_else(|| anyhow!("{} is not a valid month", month))?;
+                Ok(ActIdentifier {
+                    year,
+                    number: number.parse()?,
+                    kind: DocumentKind::from_name(
+                        decree_type,
+                        issuer.parse()?,
+                        month,
+                        day.parse()?,
+                    )
+                    .ok_or_else(|| anyhow!("Unknown decree type {}", decree_type))?,
+                })
+            }
+            _ => Err(anyhow!("Incomplete act id from grammar: {:?}", act_id)),
+        }
     }
 }
 
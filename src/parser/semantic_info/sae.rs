@@ -19,6 +19,7 @@ use hun_law_grammar::grammar_parse;
 
 use super::{
     abbreviation::{get_new_abbreviations, AbbreviationCache},
+    attachment::get_outgoing_attachment_references,
     block_amendment::{
         convert_block_amendment, convert_structural_block_amendment,
         convert_subtitle_block_amendment,
@@ -31,7 +32,7 @@ use super::{
 use crate::{
     identifier::IdentifierCommon,
     reference::Reference,
-    semantic_info::{OutgoingReference, SemanticInfo, SpecialPhrase},
+    semantic_info::{OutgoingAttachmentReference, OutgoingReference, SemanticInfo, SpecialPhrase},
     structure::{ChildrenCommon, SAEBody, SubArticleElement},
     util::walker::SAEVisitorMut,
 };
@@ -137,11 +138,25 @@ impl<'a> SemanticInfoAdder<'a> {
                 adjust_outgoing_reference(self.prefix().len(), s.len() - self.postfix().len(), oref)
             })
             .collect();
+        let outgoing_attachment_references =
+            get_outgoing_attachment_references(self.abbreviation_cache, &parsed)?
+                .into_iter()
+                .filter_map(|oref| {
+                    let (start, end) = adjust_position(
+                        self.prefix().len(),
+                        s.len() - self.postfix().len(),
+                        oref.start,
+                        oref.end,
+                    )?;
+                    Some(OutgoingAttachmentReference { start, end, ..oref })
+                })
+                .collect();
 
         let special_phrase = extract_special_phrase(self.abbreviation_cache, &parsed)
             .with_context(|| format!("Could not extract special phrase from '{s}'"))?;
         Ok(SemanticInfo {
             outgoing_references,
+            outgoing_attachment_references,
             new_abbreviations: new_abbreviations.into_iter().collect(),
             special_phrase,
         })
@@ -180,15 +195,25 @@ fn adjust_outgoing_reference(
     textlen: usize,
     oref: OutgoingReference,
 ) -> Option<OutgoingReference> {
+    let (start, end) = adjust_position(prefixlen, textlen, oref.start, oref.end)?;
+    Some(OutgoingReference {
+        start,
+        end,
+        reference: oref.reference,
+    })
+}
+
+fn adjust_position(
+    prefixlen: usize,
+    textlen: usize,
+    start: usize,
+    end: usize,
+) -> Option<(usize, usize)> {
     // The end of the parsed reference is inside the target string
     // Checking for the end and not the beginning is important, because
     // we also want partial references to work here.
-    if oref.end > prefixlen && oref.end <= textlen {
-        Some(OutgoingReference {
-            start: oref.start.saturating_sub(prefixlen),
-            end: oref.end - prefixlen,
-            reference: oref.reference,
-        })
+    if end > prefixlen && end <= textlen {
+        Some((start.saturating_sub(prefixlen), end - prefixlen))
     } else {
         None
     }
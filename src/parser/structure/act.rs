@@ -17,27 +17,56 @@ use anyhow::{bail, ensure, Result};
 
 use super::{
     article::{ArticleParser, ArticleParserFactory},
+    attachment::parse_attachments,
     structural_element::{StructuralElementParser, StructuralElementParserFactory},
     subtitle::{SubtitleParser, SubtitleParserFactory},
 };
 use crate::{
+    identifier::DocumentKind,
     parser::mk_act_section::ActRawText,
     structure::{Act, ActChild, StructuralElementType},
     util::{indentedline::IndentedLine, QuoteCheck},
 };
 
 pub fn parse_act_structure(raw_act: &ActRawText) -> Result<Act> {
-    let (preamble, children) = parse_complex_body(&raw_act.body, ParsingContext::FullAct)?;
+    let (preamble, children) = if let DocumentKind::Resolution { .. } = raw_act.identifier.kind {
+        parse_resolution_body(&raw_act.body)?
+    } else {
+        parse_complex_body(&raw_act.body, ParsingContext::FullAct)?
+    };
     Ok(Act {
         identifier: raw_act.identifier,
         subject: raw_act.subject.clone(),
         preamble,
         publication_date: raw_act.publication_date,
+        adoption_date: raw_act.adoption_date,
         contained_abbreviations: Default::default(),
         children,
+        signatories: raw_act.signatories.clone(),
+        attachments: parse_attachments(&raw_act.attachments)?,
+        footnotes: raw_act.footnotes.clone(),
     })
 }
 
+/// Most resolutions only consist of numbered points, without articles.
+/// These don't fit the usual structure, so the whole text is stored as the preamble.
+fn parse_resolution_body(lines: &[IndentedLine]) -> Result<(String, Vec<ActChild>)> {
+    let mut article_parser_factory = ArticleParserFactory::new(ParsingContext::FullAct);
+    if lines.iter().any(|line| {
+        article_parser_factory
+            .try_create_from_header(line, None)
+            .is_ok()
+    }) {
+        parse_complex_body(lines, ParsingContext::FullAct)
+    } else {
+        let mut preamble = String::new();
+        for line in lines {
+            line.append_to(&mut preamble);
+        }
+        Ok((preamble, Vec::new()))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParsingContext {
     FullAct,
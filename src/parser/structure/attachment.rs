@@ -0,0 +1,138 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{anyhow, Result};
+use lazy_regex::{regex_captures, regex_is_match};
+
+use super::{
+    act::ParsingContext,
+    sae::{AlphabeticPointParser, NumericPointParser, SAEParseParams, SAEParser},
+};
+use crate::{
+    identifier::{IdentifierCommon, NumericIdentifier},
+    structure::{Attachment, ParagraphChildren},
+    util::indentedline::IndentedLine,
+};
+
+/// Parse the text after the signatures of an act into attachments.
+///
+/// Attachments start with a header after an empty line, in the form of
+/// "1. melléklet a 2013. évi V. törvényhez". Single attachments are not numbered:
+/// "Melléklet a 2013. évi V. törvényhez".
+pub fn parse_attachments(lines: &[IndentedLine]) -> Result<Vec<Attachment>> {
+    let mut attachments: Vec<(NumericIdentifier, Vec<IndentedLine>)> = Vec::new();
+    let mut prev_line_is_empty = true;
+    for line in lines {
+        let last_identifier = attachments.last().map(|(id, _)| *id);
+        let new_identifier = if prev_line_is_empty {
+            parse_attachment_header(line).filter(|id| match last_identifier {
+                Some(last_identifier) => id.is_next_from(last_identifier),
+                None => id.is_first(),
+            })
+        } else {
+            None
+        };
+        if let Some(identifier) = new_identifier {
+            attachments.push((identifier, vec![line.clone()]));
+        } else if let Some((_, attachment_lines)) = attachments.last_mut() {
+            attachment_lines.push(line.clone());
+        } else if !line.is_empty() {
+            return Err(anyhow!(
+                "Text before the first attachment header: '{}'",
+                line.content()
+            ));
+        }
+        prev_line_is_empty = line.is_empty();
+    }
+    attachments
+        .into_iter()
+        .map(|(identifier, lines)| parse_attachment(identifier, &lines))
+        .collect()
+}
+
+fn parse_attachment_header(line: &IndentedLine) -> Option<NumericIdentifier> {
+    let (_, identifier) = regex_captures!("^(?:([0-9]+)\\. )?[mM]elléklet az? ", line.content())?;
+    if identifier.is_empty() {
+        Some(1.into())
+    } else {
+        identifier.parse().ok()
+    }
+}
+
+fn parse_attachment(identifier: NumericIdentifier, lines: &[IndentedLine]) -> Result<Attachment> {
+    // The header may be broken into multiple lines, but it always ends with
+    // the act identifier with a suffix, e.g. "törvényhez" or "rendelethez".
+    // The title is bold, while the header is not, so don't go into it.
+    let mut header = lines[0].content().to_owned();
+    let mut body_start = 1;
+    while !regex_is_match!("(hoz|hez|höz)$", &header)
+        && body_start < lines.len()
+        && !lines[body_start].is_empty()
+        && lines[body_start].is_bold() == lines[0].is_bold()
+    {
+        lines[body_start].append_to(&mut header);
+        body_start += 1;
+    }
+    let mut title = String::new();
+    let mut lines = &lines[body_start..];
+    while let Some((line, rest)) = lines.split_first() {
+        if line.is_empty() && title.is_empty() {
+            lines = rest;
+        } else if line.is_bold() && !line.is_empty() {
+            line.append_to(&mut title);
+            lines = rest;
+        } else {
+            break;
+        }
+    }
+    while let Some((line, rest)) = lines.split_first() {
+        if !line.is_empty() {
+            break;
+        }
+        lines = rest;
+    }
+    while let Some((line, rest)) = lines.split_last() {
+        if !line.is_empty() {
+            break;
+        }
+        lines = rest;
+    }
+    Ok(Attachment {
+        identifier,
+        title,
+        lines: lines.iter().map(|l| l.content().to_owned()).collect(),
+        children: parse_attachment_children(lines),
+        last_change: None,
+    })
+}
+
+/// Try to parse the body as a simple list of points. Returns None for
+/// everything else (tables, forms, intro text, etc.)
+fn parse_attachment_children(lines: &[IndentedLine]) -> Option<ParagraphChildren> {
+    if lines.is_empty() {
+        return None;
+    }
+    let params = SAEParseParams {
+        parse_wrap_up: false,
+        check_children_count: true,
+        context: ParsingContext::FullAct,
+    };
+    NumericPointParser
+        .extract_multiple(lines, &params, None)
+        .or_else(|_| AlphabeticPointParser.extract_multiple(lines, &params, None))
+        .ok()
+        .map(|extracted| extracted.elements)
+}
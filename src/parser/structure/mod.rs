@@ -15,6 +15,7 @@
 // along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
 mod act;
 mod article;
+mod attachment;
 mod block_amendment;
 mod quote;
 mod sae;
@@ -22,3 +23,4 @@ mod structural_element;
 mod subtitle;
 
 pub use act::parse_act_structure;
+pub use attachment::parse_attachments;
@@ -112,6 +112,7 @@ mod tests {
         builder.set_part(ActIdentifier {
             year: 2001,
             number: 420,
+            ..Default::default()
         });
         builder.set_part(RefPartArticle::from_single("4:20".parse().unwrap()));
         // Test chaining
@@ -130,6 +131,7 @@ mod tests {
                 act: Some(ActIdentifier {
                     year: 2001,
                     number: 420,
+                    ..Default::default()
                 }),
                 article: Some(RefPartArticle::from_single("4:20".parse().unwrap())),
                 paragraph: Some(RefPartParagraph::from_single("20".parse().unwrap())),
@@ -153,6 +155,7 @@ mod tests {
                 act: Some(ActIdentifier {
                     year: 2001,
                     number: 420,
+                    ..Default::default()
                 }),
                 article: Some(RefPartArticle::from_range(
                     "1:10".parse().unwrap(),
@@ -0,0 +1,124 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::Reference;
+use crate::{
+    identifier::{ActIdentifier, IdentifierCommon},
+    structure::{Act, ChildrenCommon, SubArticleElement},
+    util::walker::SAEVisitor,
+};
+
+/// A reference found in an act, as seen from the referenced element
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncomingReference {
+    /// The absolute position of the SAE containing the reference
+    pub citing_position: Reference,
+    /// Byte index of the first character of the reference string in the citing SAE
+    pub start: usize,
+    /// Byte index after the last character of the reference string in the citing SAE
+    pub end: usize,
+    /// The absolute reference to the cited element
+    pub target: Reference,
+}
+
+/// Reverse index of the outgoing references of a set of acts
+#[derive(Debug, Clone, Default)]
+pub struct IncomingReferenceIndex {
+    references: BTreeMap<ActIdentifier, Vec<IncomingReference>>,
+}
+
+impl IncomingReferenceIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_acts<'a>(acts: impl IntoIterator<Item = &'a Act>) -> Result<Self> {
+        let mut result = Self::new();
+        for act in acts {
+            result.add_act(act)?;
+        }
+        Ok(result)
+    }
+
+    /// Add all outgoing references of an act with semantic info to the index.
+    ///
+    /// Internal references are made absolute based on the position of the citing element.
+    pub fn add_act(&mut self, act: &Act) -> Result<()> {
+        let mut visitor = IncomingReferenceCollector::default();
+        act.walk_saes(&mut visitor)?;
+        for incoming in visitor.result {
+            let target_act = incoming
+                .target
+                .act()
+                .ok_or_else(|| anyhow!("Could not resolve reference {:?}", incoming.target))?;
+            self.references
+                .entry(target_act)
+                .or_default()
+                .push(incoming);
+        }
+        Ok(())
+    }
+
+    /// All references that cite `target`, its children, or one of its parents.
+    ///
+    /// References to the act as a whole (e.g. the title of the act) are only returned
+    /// if `target` is an act-only reference.
+    pub fn get(&self, target: &Reference) -> impl Iterator<Item = &IncomingReference> {
+        let target = target.clone();
+        target
+            .act()
+            .and_then(|act| self.references.get(&act))
+            .into_iter()
+            .flatten()
+            .filter(move |incoming| {
+                target.contains(&incoming.target)
+                    || (!incoming.target.is_act_only() && incoming.target.contains(&target))
+            })
+    }
+
+    /// Iterate over all references in the index, grouped by the cited act
+    pub fn iter(&self) -> impl Iterator<Item = &IncomingReference> {
+        self.references.values().flatten()
+    }
+}
+
+#[derive(Debug, Default)]
+struct IncomingReferenceCollector {
+    result: Vec<IncomingReference>,
+}
+
+impl SAEVisitor for IncomingReferenceCollector {
+    fn on_enter<IT: IdentifierCommon, CT: ChildrenCommon>(
+        &mut self,
+        position: &Reference,
+        element: &SubArticleElement<IT, CT>,
+    ) -> Result<()> {
+        for outgoing in &element.semantic_info.outgoing_references {
+            self.result.push(IncomingReference {
+                citing_position: position.clone(),
+                start: outgoing.start,
+                end: outgoing.end,
+                target: outgoing.reference.relative_to(position)?,
+            });
+        }
+        Ok(())
+    }
+}
@@ -14,7 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
 
+pub mod attachment;
 pub mod builder;
+pub mod incoming;
 pub mod parts;
 pub mod structural;
 pub mod to_element;
@@ -80,6 +82,18 @@ impl Reference {
         self.article
     }
 
+    pub fn paragraph(&self) -> Option<RefPartParagraph> {
+        self.paragraph
+    }
+
+    pub fn point(&self) -> Option<RefPartPoint> {
+        self.point
+    }
+
+    pub fn subpoint(&self) -> Option<RefPartSubpoint> {
+        self.subpoint
+    }
+
     pub fn without_act(&self) -> Reference {
         // This is done without going through an UncheckedReference because
         // there is no way to create an invalid Reference like this.
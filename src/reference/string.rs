@@ -216,6 +216,7 @@ mod tests {
             act: Some(ActIdentifier {
                 year: 2012,
                 number: 1,
+                ..Default::default()
             }),
             article: quick_convert_part("1"),
             paragraph: quick_convert_part("2"),
@@ -272,6 +273,7 @@ mod tests {
             act: Some(ActIdentifier {
                 year: 2012,
                 number: 1,
+                ..Default::default()
             }),
             article: quick_convert_part("1"),
             paragraph: quick_convert_part("2"),
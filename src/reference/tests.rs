@@ -28,6 +28,7 @@ fn test_make_range() {
     builder.set_part(ActIdentifier {
         year: 2001,
         number: 420,
+        ..Default::default()
     });
     let ref_act = builder.build().unwrap();
 
@@ -241,7 +242,8 @@ fn test_ordering() {
         Reference {
             act: Some(ActIdentifier {
                 year: 2000,
-                number: 1
+                number: 1,
+                ..Default::default()
             }),
             ..Default::default()
         } > Reference {
@@ -324,6 +326,7 @@ fn test_contains() {
         act: Some(ActIdentifier {
             year: 2012,
             number: 1,
+            ..Default::default()
         }),
         article: quick_convert_part("1"),
         ..Default::default()
@@ -332,6 +335,7 @@ fn test_contains() {
         act: Some(ActIdentifier {
             year: 2012,
             number: 2,
+            ..Default::default()
         }),
         article: quick_convert_part("1"),
         ..Default::default()
@@ -343,6 +347,7 @@ fn test_contains() {
         act: Some(ActIdentifier {
             year: 2012,
             number: 1,
+            ..Default::default()
         }),
         article: quick_convert_part("1"),
         paragraph: quick_convert_part("1"),
@@ -353,6 +358,7 @@ fn test_contains() {
         act: Some(ActIdentifier {
             year: 2012,
             number: 1,
+            ..Default::default()
         }),
         article: quick_convert_part("1"),
         paragraph: quick_convert_part("1"),
@@ -368,6 +374,7 @@ fn test_contains() {
         act: Some(ActIdentifier {
             year: 2012,
             number: 1,
+            ..Default::default()
         }),
         article: quick_convert_part("1"),
         paragraph: quick_convert_part("1"),
@@ -378,6 +385,7 @@ fn test_contains() {
         act: Some(ActIdentifier {
             year: 2012,
             number: 1,
+            ..Default::default()
         }),
         article: quick_convert_part("1"),
         paragraph: quick_convert_part("1"),
@@ -395,6 +403,7 @@ fn test_relative_to() {
         act: Some(ActIdentifier {
             year: 2012,
             number: 1,
+            ..Default::default()
         }),
         article: quick_convert_part("1"),
         paragraph: quick_convert_part("1"),
@@ -410,6 +419,7 @@ fn test_relative_to() {
             act: Some(ActIdentifier {
                 year: 2014,
                 number: 1,
+                ..Default::default()
             }),
             ..Default::default()
         }
@@ -419,6 +429,7 @@ fn test_relative_to() {
             act: Some(ActIdentifier {
                 year: 2014,
                 number: 1,
+                ..Default::default()
             }),
             ..Default::default()
         }
@@ -434,6 +445,7 @@ fn test_relative_to() {
             act: Some(ActIdentifier {
                 year: 2012,
                 number: 1,
+                ..Default::default()
             }),
             article: quick_convert_part("5"),
             ..Default::default()
@@ -450,6 +462,7 @@ fn test_relative_to() {
             act: Some(ActIdentifier {
                 year: 2012,
                 number: 1,
+                ..Default::default()
             }),
             article: quick_convert_part("1"),
             paragraph: quick_convert_part("5"),
@@ -467,6 +480,7 @@ fn test_relative_to() {
             act: Some(ActIdentifier {
                 year: 2012,
                 number: 1,
+                ..Default::default()
             }),
             article: quick_convert_part("1"),
             paragraph: quick_convert_part("1"),
@@ -485,6 +499,7 @@ fn test_relative_to() {
             act: Some(ActIdentifier {
                 year: 2012,
                 number: 1,
+                ..Default::default()
             }),
             article: quick_convert_part("1"),
             paragraph: quick_convert_part("1"),
@@ -527,6 +542,7 @@ fn test_parent() {
         act: Some(ActIdentifier {
             year: 2012,
             number: 1,
+            ..Default::default()
         }),
         article: quick_convert_part("1"),
         paragraph: quick_convert_part("1"),
@@ -539,6 +555,7 @@ fn test_parent() {
             act: Some(ActIdentifier {
                 year: 2012,
                 number: 1,
+                ..Default::default()
             }),
             article: quick_convert_part("1"),
             paragraph: quick_convert_part("1"),
@@ -552,6 +569,7 @@ fn test_parent() {
             act: Some(ActIdentifier {
                 year: 2012,
                 number: 1,
+                ..Default::default()
             }),
             article: quick_convert_part("1"),
             paragraph: quick_convert_part("1"),
@@ -564,6 +582,7 @@ fn test_parent() {
             act: Some(ActIdentifier {
                 year: 2012,
                 number: 1,
+                ..Default::default()
             }),
             article: quick_convert_part("1"),
             ..Default::default()
@@ -575,6 +594,7 @@ fn test_parent() {
             act: Some(ActIdentifier {
                 year: 2012,
                 number: 1,
+                ..Default::default()
             }),
             ..Default::default()
         }
@@ -21,12 +21,16 @@ use from_variants::FromVariants;
 use serde::{Deserialize, Serialize};
 
 use crate::identifier::ActIdentifier;
-use crate::reference::{structural::StructuralReference, Reference};
+use crate::reference::{
+    attachment::AttachmentReference, structural::StructuralReference, Reference,
+};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SemanticInfo {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub outgoing_references: Vec<OutgoingReference>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outgoing_attachment_references: Vec<OutgoingAttachmentReference>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub new_abbreviations: BTreeMap<String, ActIdentifier>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -36,6 +40,7 @@ pub struct SemanticInfo {
 impl SemanticInfo {
     pub fn is_empty(&self) -> bool {
         self.outgoing_references.is_empty()
+            && self.outgoing_attachment_references.is_empty()
             && self.new_abbreviations.is_empty()
             && self.special_phrase.is_none()
     }
@@ -62,6 +67,15 @@ impl<'a> From<&'a OutgoingReference> for &'a Reference {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OutgoingAttachmentReference {
+    /// Byte index of the first character of the attachment number
+    pub start: usize,
+    /// Byte index after the last character of the attachment number
+    pub end: usize,
+    pub reference: AttachmentReference,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, FromVariants)]
 pub enum SpecialPhrase {
     BlockAmendment(BlockAmendment),
@@ -109,6 +123,8 @@ pub struct EnforcementDate {
     pub positions: Vec<Reference>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub structural_positions: Vec<StructuralReference>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachment_positions: Vec<AttachmentReference>,
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub is_default: bool,
     pub date: EnforcementDateType,
@@ -89,9 +89,18 @@ pub struct Act {
     pub subject: String,
     pub preamble: String,
     pub publication_date: NaiveDate,
+    /// The date the act was adopted by the Parliament, if it was in the footnotes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adoption_date: Option<NaiveDate>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub contained_abbreviations: BTreeMap<String, ActIdentifier>,
     pub children: Vec<ActChild>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatories: Vec<Signatory>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub footnotes: Vec<Footnote>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromVariants)]
@@ -126,6 +135,84 @@ impl Act {
     pub fn article_mut(&mut self, article_id: ArticleIdentifier) -> Option<&mut Article> {
         self.articles_mut().find(|a| a.identifier == article_id)
     }
+    pub fn attachment(&self, attachment_id: NumericIdentifier) -> Option<&Attachment> {
+        self.attachments
+            .iter()
+            .find(|a| a.identifier == attachment_id)
+    }
+}
+
+// The officials signing the act at the end of its text. Example:
+// Dr. Schmitt Pál s. k.,     Dr. Kövér László s. k.,
+//  köztársasági elnök        az Országgyűlés elnöke
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signatory {
+    pub name: String,
+    pub role: String,
+}
+
+// Attachments ("mellékletek") come after the signatures, and usually contain
+// lists, tables or forms. Tables and forms are kept as raw lines, but if the
+// attachment is a simple list of points, it is parsed into the usual SAEs.
+// Example:
+// 1. melléklet a 2013. évi V. törvényhez
+// A díjak mértéke
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub identifier: NumericIdentifier,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    pub lines: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub children: Option<ParagraphChildren>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_change: Option<LastChange>,
+}
+
+impl Attachment {
+    pub fn header_string(&self) -> String {
+        format!("{}. melléklet", self.identifier)
+    }
+}
+
+// Footnotes at the bottom of the pages in the original publication. Example:
+// * A törvényt az Országgyűlés a 2010. november 22-i ülésnapján fogadta el.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Footnote {
+    /// The marker used in the text, e.g. "*" or "1"
+    pub marker: String,
+    pub text: String,
+}
+
+// Tables are detected based on the drawn rule lines in the PDF. Cells spanning
+// multiple columns or rows are only stored once, at their top left position,
+// the same way as in HTML.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Table {
+    pub rows: Vec<TableRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableRow {
+    pub cells: Vec<TableCell>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableCell {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+    #[serde(default = "span_default", skip_serializing_if = "span_is_default")]
+    pub colspan: usize,
+    #[serde(default = "span_default", skip_serializing_if = "span_is_default")]
+    pub rowspan: usize,
+}
+
+fn span_default() -> usize {
+    1
+}
+
+fn span_is_default(span: &usize) -> bool {
+    *span == 1
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -507,6 +594,8 @@ pub struct LastChange {
 pub enum ChangeCause {
     Amendment(Reference),
     AutoRepeal,
+    /// Repealed by the act's own enforcement date provision ("...-én hatályát veszti")
+    InlineRepeal,
     Other(String),
 }
 
@@ -0,0 +1,255 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use from_variants::FromVariants;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    amender::structural_amendment::{find_existing, find_parent_range},
+    identifier::{ActIdentifier, IdentifierCommon},
+    reference::{structural::StructuralReference, to_element::ReferenceToElement, Reference},
+    semantic_info::{RepealReference, SpecialPhrase, TextAmendmentReference},
+    structure::{Act, ChildrenCommon, SubArticleElement},
+    util::{
+        debug::DebugContextString,
+        walker::{SAEVisitor, WalkSAE},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReferenceKind {
+    Outgoing,
+    BlockAmendment,
+    StructuralBlockAmendment,
+    TextAmendment,
+    Repeal,
+    EnforcementDate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromVariants)]
+pub enum ReferenceTarget {
+    Reference(Reference),
+    StructuralReference(StructuralReference),
+}
+
+/// A reference that does not point to an existing element
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DanglingReference {
+    /// The position of the element containing the reference
+    pub position: Reference,
+    /// Debug context of the element containing the reference
+    pub context: String,
+    pub kind: ReferenceKind,
+    pub target: ReferenceTarget,
+}
+
+impl Act {
+    /// Check that all references and amendment positions in the act point to existing elements.
+    ///
+    /// References to other acts are only checked if the referenced act is in `external_acts`.
+    /// Requires semantic info to be present.
+    pub fn validate_references<'a>(
+        &self,
+        external_acts: impl IntoIterator<Item = &'a Act>,
+    ) -> Result<Vec<DanglingReference>> {
+        let mut known_acts = KnownActs::default();
+        known_acts.add(self)?;
+        for act in external_acts {
+            if act.identifier != self.identifier {
+                known_acts.add(act)?;
+            }
+        }
+        let mut visitor = ValidatingVisitor {
+            act_id: self.identifier,
+            known_acts: &known_acts,
+            result: Vec::new(),
+        };
+        self.walk_saes(&mut visitor)?;
+        Ok(visitor.result)
+    }
+}
+
+#[derive(Debug, Default)]
+struct KnownActs<'a> {
+    acts: BTreeMap<ActIdentifier, (&'a Act, BTreeSet<Reference>)>,
+}
+
+impl<'a> KnownActs<'a> {
+    fn add(&mut self, act: &'a Act) -> Result<()> {
+        let mut collector = ElementCollector::default();
+        for article in act.articles() {
+            let article_reference = article.reference();
+            collector.result.insert(article_reference.clone());
+            article
+                .children
+                .walk_saes(&article_reference, &mut collector)?;
+        }
+        self.acts.insert(act.identifier, (act, collector.result));
+        Ok(())
+    }
+
+    /// Returns None if the referenced act is not known
+    fn reference_exists(&self, reference: &Reference) -> Option<bool> {
+        let (_, elements) = self.acts.get(&reference.act()?)?;
+        let reference = reference.without_act();
+        Some(
+            reference.is_act_only()
+                || (elements.contains(&reference.first_in_range())
+                    && elements.contains(&reference.last_in_range())),
+        )
+    }
+
+    /// Returns None if the referenced act is not known
+    fn structural_reference_exists(&self, reference: &StructuralReference) -> Option<bool> {
+        let (act, _) = self.acts.get(&reference.act?)?;
+        Some(
+            find_parent_range(&act.children, reference)
+                .and_then(|within| {
+                    find_existing(&act.children, within, &reference.structural_element, &[])
+                })
+                .is_ok(),
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct ElementCollector {
+    result: BTreeSet<Reference>,
+}
+
+impl SAEVisitor for ElementCollector {
+    fn on_enter<IT: IdentifierCommon, CT: ChildrenCommon>(
+        &mut self,
+        position: &Reference,
+        _element: &SubArticleElement<IT, CT>,
+    ) -> Result<()> {
+        self.result.insert(position.clone());
+        Ok(())
+    }
+}
+
+struct ValidatingVisitor<'a> {
+    act_id: ActIdentifier,
+    known_acts: &'a KnownActs<'a>,
+    result: Vec<DanglingReference>,
+}
+
+impl<'a> SAEVisitor for ValidatingVisitor<'a> {
+    fn on_enter<IT: IdentifierCommon, CT: ChildrenCommon>(
+        &mut self,
+        position: &Reference,
+        element: &SubArticleElement<IT, CT>,
+    ) -> Result<()> {
+        let mut targets: Vec<(ReferenceKind, ReferenceTarget)> = Vec::new();
+        for outgoing in &element.semantic_info.outgoing_references {
+            targets.push((
+                ReferenceKind::Outgoing,
+                outgoing.reference.relative_to(position)?.into(),
+            ));
+        }
+        match &element.semantic_info.special_phrase {
+            Some(SpecialPhrase::BlockAmendment(ba)) => {
+                let ba_position = ba.position.relative_to(position)?;
+                // Only the parent has to exist for insertions
+                let target = if ba.pure_insertion {
+                    ba_position.parent()
+                } else {
+                    ba_position
+                };
+                targets.push((ReferenceKind::BlockAmendment, target.into()));
+            }
+            // Insertion points are not checked for structural amendments
+            Some(SpecialPhrase::StructuralBlockAmendment(sba)) if !sba.pure_insertion => {
+                targets.push((
+                    ReferenceKind::StructuralBlockAmendment,
+                    self.absolute_structural(&sba.position).into(),
+                ));
+            }
+            Some(SpecialPhrase::TextAmendment(text_amendments)) => {
+                for text_amendment in text_amendments {
+                    let target = match &text_amendment.reference {
+                        TextAmendmentReference::SAE { reference, .. }
+                        | TextAmendmentReference::ArticleTitle(reference) => {
+                            reference.relative_to(position)?.into()
+                        }
+                        TextAmendmentReference::Structural(reference) => {
+                            self.absolute_structural(reference).into()
+                        }
+                    };
+                    targets.push((ReferenceKind::TextAmendment, target));
+                }
+            }
+            Some(SpecialPhrase::Repeal(repeals)) => {
+                for repeal in repeals {
+                    let target = match repeal {
+                        RepealReference::Reference(reference) => {
+                            reference.relative_to(position)?.into()
+                        }
+                        RepealReference::StructuralReference(reference) => {
+                            self.absolute_structural(reference).into()
+                        }
+                    };
+                    targets.push((ReferenceKind::Repeal, target));
+                }
+            }
+            Some(SpecialPhrase::EnforcementDate(ed)) => {
+                for ed_position in &ed.positions {
+                    targets.push((
+                        ReferenceKind::EnforcementDate,
+                        ed_position.relative_to(position)?.into(),
+                    ));
+                }
+                for structural_position in &ed.structural_positions {
+                    targets.push((
+                        ReferenceKind::EnforcementDate,
+                        self.absolute_structural(structural_position).into(),
+                    ));
+                }
+            }
+            _ => (),
+        }
+        for (kind, target) in targets {
+            let exists = match &target {
+                ReferenceTarget::Reference(r) => self.known_acts.reference_exists(r),
+                ReferenceTarget::StructuralReference(r) => {
+                    self.known_acts.structural_reference_exists(r)
+                }
+            };
+            if exists == Some(false) {
+                self.result.push(DanglingReference {
+                    position: position.clone(),
+                    context: element.debug_ctx(),
+                    kind,
+                    target,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ValidatingVisitor<'a> {
+    /// Structural references without an act are internal references
+    fn absolute_structural(&self, reference: &StructuralReference) -> StructuralReference {
+        StructuralReference {
+            act: reference.act.or(Some(self.act_id)),
+            ..reference.clone()
+        }
+    }
+}
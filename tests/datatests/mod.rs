@@ -15,7 +15,14 @@
 // along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
 
 pub mod test_add_semantic_info;
+pub mod test_amender;
+pub mod test_attachment_parser;
+pub mod test_auto_repeal;
 pub mod test_convert_block_amendments;
+pub mod test_enforcement_date;
+pub mod test_incoming_references;
 pub mod test_pdf_parser;
 pub mod test_semantic_parser;
+pub mod test_snapshot;
 pub mod test_structure_parser;
+pub mod test_validation;
@@ -0,0 +1,46 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::path::Path;
+
+use chrono::NaiveDate;
+use datatest_stable::Result;
+use hun_law::{amender::AppliableModification, structure::Act, util::singleton_yaml};
+use serde::Deserialize;
+
+use crate::declare_test;
+use crate::test_utils::{ensure_eq, read_all};
+
+declare_test!(dir = "data_amender", pattern = r"\.yml");
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TestCase {
+    pub date: NaiveDate,
+    pub act: Act,
+    pub modifications: Vec<AppliableModification>,
+    pub expected: Act,
+}
+
+pub fn run_test(path: &Path) -> Result<()> {
+    let test_case: TestCase = singleton_yaml::from_slice(&read_all(path)?)?;
+    let mut act = test_case.act;
+    for modification in &test_case.modifications {
+        modification.apply(&mut act, test_case.date)?;
+    }
+    ensure_eq(&test_case.expected, &act, "Wrong act contents")?;
+    Ok(())
+}
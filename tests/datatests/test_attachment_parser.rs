@@ -0,0 +1,33 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::path::Path;
+
+use datatest_stable::Result;
+use hun_law::{parser::structure::parse_attachments, structure::Attachment, util::singleton_yaml};
+
+use crate::declare_test;
+use crate::test_utils::{ensure_eq, read_all, to_indented_lines};
+
+declare_test!(dir = "data_attachment_parser", pattern = r"\.txt");
+
+pub fn run_test(path: &Path) -> Result<()> {
+    let attachments = parse_attachments(&to_indented_lines(&read_all(path)?))?;
+    let expected: Vec<Attachment> =
+        singleton_yaml::from_slice(&read_all(path.with_extension("yml"))?)?;
+    ensure_eq(&expected, &attachments, "Wrong attachments")?;
+    Ok(())
+}
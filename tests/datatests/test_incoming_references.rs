@@ -0,0 +1,61 @@
+// Copyright (C) 2022, Alex Badics
+//
+// This file is part of Hun-Law.
+//
+// Hun-law is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Hun-law is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Hun-law. If not, see <http://www.gnu.org/licenses/>.
+
+use std::path::Path;
+
+use datatest_stable::Result;
+use hun_law::{
+    reference::{
+        incoming::{IncomingReference, IncomingReferenceIndex},
+        Reference,
+    },
+    structure::Act,
+    util::singleton_yaml,
+};
+use serde::Deserialize;
+
+use crate::declare_test;
+use crate::test_utils::{ensure_eq, read_all};
+
+declare_test!(dir = "data_incoming_references", pattern = r"\.yml");
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TestCase {
+    pub acts: Vec<Act>,
+    pub queries: Vec<Query>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Query {
+    pub target: Reference,
+    pub expected: Vec<IncomingReference>,
+}
+
+pub fn run_test(path: &Path) -> Result<()> {
+    let test_case: TestCase = singleton_yaml::from_slice(&read_all(path)?)?;
+    let index = IncomingReferenceIndex::from_acts(&test_case.acts)?;
+    for query in test_case.queries {
+        let result: Vec<_> = index.get(&query.target).cloned().collect();
+        ensure_eq(
+            &query.expected,
+            &result,
+            &format!("Wrong incoming references for {:?}", query.target),
+        )?;
+    }
+    Ok(())
+}
@@ -20,8 +20,8 @@ use datatest_stable::Result;
 use hun_law::{
     identifier::ActIdentifier,
     parser::semantic_info::{abbreviation::AbbreviationCache, sae::SemanticInfoAdder},
-    reference::Reference,
-    semantic_info::{OutgoingReference, SpecialPhrase},
+    reference::{attachment::AttachmentReference, Reference},
+    semantic_info::{OutgoingAttachmentReference, OutgoingReference, SpecialPhrase},
     util::singleton_yaml,
 };
 use serde::{Deserialize, Serialize};
@@ -42,6 +42,8 @@ struct TestCase {
     pub expected_new_abbreviations: BTreeMap<String, ActIdentifier>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub expected_references: Vec<Reference>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expected_attachment_references: Vec<AttachmentReference>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub expected_special_phrase: Option<SpecialPhrase>,
 }
@@ -52,8 +54,11 @@ pub fn run_test(path: &Path) -> Result<()> {
     let mut visitor = SemanticInfoAdder::new(&mut abbreviation_cache);
     let semantic_info = visitor.extract_semantic_info(&test_case.text)?;
 
-    let (expected_references, positions) =
-        convert_references(&semantic_info.outgoing_references, &test_case.text);
+    let (expected_references, expected_attachment_references, positions) = convert_references(
+        &semantic_info.outgoing_references,
+        &semantic_info.outgoing_attachment_references,
+        &test_case.text,
+    );
 
     let result = TestCase {
         text: test_case.text.clone(),
@@ -61,6 +66,7 @@ pub fn run_test(path: &Path) -> Result<()> {
         abbreviations: test_case.abbreviations.clone(),
         expected_new_abbreviations: semantic_info.new_abbreviations,
         expected_references,
+        expected_attachment_references,
         expected_special_phrase: semantic_info.special_phrase,
     };
     ensure_eq(&test_case, &result, "Semantic info incorrect")?;
@@ -69,25 +75,39 @@ pub fn run_test(path: &Path) -> Result<()> {
 
 fn convert_references(
     outgoing_references: &[OutgoingReference],
+    outgoing_attachment_references: &[OutgoingAttachmentReference],
     text: &str,
-) -> (Vec<Reference>, String) {
+) -> (Vec<Reference>, Vec<AttachmentReference>, String) {
     let mut parsed_refs = Vec::new();
+    let mut parsed_attachment_refs = Vec::new();
     let mut parsed_positions = vec![b' '; text.chars().count()];
 
     for outgoing_reference in outgoing_references {
         parsed_refs.push(outgoing_reference.reference.clone());
-        let start_char_index = text
-            .char_indices()
-            .position(|(cp, _)| cp == outgoing_reference.start)
-            .unwrap();
-        let end_char_index = text
-            .char_indices()
-            .position(|(cp, _)| cp == outgoing_reference.end)
-            .unwrap();
-        parsed_positions[start_char_index] = b'<';
-        parsed_positions[end_char_index - 1] = b'>';
+        mark_position(
+            &mut parsed_positions,
+            text,
+            outgoing_reference.start,
+            outgoing_reference.end,
+        );
+    }
+    for outgoing_reference in outgoing_attachment_references {
+        parsed_attachment_refs.push(outgoing_reference.reference.clone());
+        mark_position(
+            &mut parsed_positions,
+            text,
+            outgoing_reference.start,
+            outgoing_reference.end,
+        );
     }
 
     let parsed_positions = String::from_utf8(parsed_positions).unwrap();
-    (parsed_refs, parsed_positions)
+    (parsed_refs, parsed_attachment_refs, parsed_positions)
+}
+
+fn mark_position(parsed_positions: &mut [u8], text: &str, start: usize, end: usize) {
+    let start_char_index = text.char_indices().position(|(cp, _)| cp == start).unwrap();
+    let end_char_index = text.char_indices().position(|(cp, _)| cp == end).unwrap();
+    parsed_positions[start_char_index] = b'<';
+    parsed_positions[end_char_index - 1] = b'>';
 }
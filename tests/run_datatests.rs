@@ -51,7 +51,14 @@ macro_rules! generate_harness{
 generate_harness!(
     test_pdf_parser,
     test_structure_parser,
+    test_attachment_parser,
     test_semantic_parser,
     test_add_semantic_info,
     test_convert_block_amendments,
+    test_amender,
+    test_auto_repeal,
+    test_enforcement_date,
+    test_snapshot,
+    test_incoming_references,
+    test_validation,
 );
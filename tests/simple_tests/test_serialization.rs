@@ -28,8 +28,8 @@ use hun_law::{
         Reference,
     },
     structure::{
-        Act, AlphabeticPoint, AlphabeticSubpoint, Article, NumericPoint, Paragraph, SAEBody,
-        StructuralElement, StructuralElementType, Subtitle,
+        Act, AlphabeticPoint, AlphabeticSubpoint, Article, Attachment, Footnote, NumericPoint,
+        Paragraph, SAEBody, Signatory, StructuralElement, StructuralElementType, Subtitle,
     },
     util::singleton_yaml,
 };
@@ -40,11 +40,13 @@ fn get_test_act() -> Act {
         identifier: ActIdentifier {
             year: 2345,
             number: 0xd,
+            ..Default::default()
         },
         publication_date: NaiveDate::from_ymd(2345, 6, 7),
+        adoption_date: Some(NaiveDate::from_ymd(2345, 5, 30)),
         subject: "A tesztelésről".into(),
         preamble: "A tesztelés nagyon fontos, és egyben kötelező".into(),
-        contained_abbreviations: [("Btk.".to_string(), ActIdentifier{year: 2012, number: 100})].into_iter().collect(),
+        contained_abbreviations: [("Btk.".to_string(), ActIdentifier{year: 2012, number: 100, ..Default::default()})].into_iter().collect(),
         children: vec![
             StructuralElement {
                 identifier: "1".parse().unwrap(),
@@ -221,6 +223,21 @@ fn get_test_act() -> Act {
             }
             .into(),
         ],
+        signatories: vec![Signatory {
+            name: "Dr. Teszt Elek".into(),
+            role: "köztársasági elnök".into(),
+        }],
+        attachments: vec![Attachment {
+            identifier: 1.into(),
+            title: "A díjak".into(),
+            lines: vec!["Megnevezés Díj".into(), "Tesztelés 100 Ft".into()],
+            children: None,
+            last_change: None,
+        }],
+        footnotes: vec![Footnote {
+            marker: "*".into(),
+            text: "A törvényt az Országgyűlés a 2345. május 30-i ülésnapján fogadta el.".into(),
+        }],
     }
 }
 
@@ -230,6 +247,7 @@ const YAML_SERIALIZED_ACT: &str = r#"identifier:
 subject: A tesztelésről
 preamble: A tesztelés nagyon fontos, és egyben kötelező
 publication_date: 2345-06-07
+adoption_date: 2345-05-30
 contained_abbreviations:
   Btk.:
     year: 2012
@@ -311,6 +329,18 @@ children:
           - identifier: '2'
             body: Alphabetic points
         wrap_up: Can also be amended
+signatories:
+- name: Dr. Teszt Elek
+  role: köztársasági elnök
+attachments:
+- identifier: '1'
+  title: A díjak
+  lines:
+  - Megnevezés Díj
+  - Tesztelés 100 Ft
+footnotes:
+- marker: '*'
+  text: A törvényt az Országgyűlés a 2345. május 30-i ülésnapján fogadta el.
 "#;
 
 #[test]
@@ -337,6 +367,7 @@ fn test_reference_serialization() {
             .set_part(ActIdentifier {
                 year: 2012,
                 number: 123,
+                ..Default::default()
             })
             .set_part(RefPartArticle::from_single("1:23/B".parse().unwrap()))
             .set_part(RefPartParagraph::from_single("2b".parse().unwrap()))
@@ -368,6 +399,7 @@ fn test_reference_serialization() {
             .set_part(ActIdentifier {
                 year: 2012,
                 number: 123,
+                ..Default::default()
             })
             .build()
             .unwrap(),
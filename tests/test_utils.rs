@@ -118,10 +118,17 @@ pub fn parse_txt_as_act(path: &Path) -> Result<Act> {
         identifier: ActIdentifier {
             year: 2345,
             number: 0xd,
+            ..Default::default()
         },
         subject: "A tesztelésről".to_string(),
         publication_date: NaiveDate::from_ymd(2345, 6, 7),
+        first_page: 0,
+        last_page: 0,
         body: data_as_lines,
+        signatories: Vec::new(),
+        attachments: Vec::new(),
+        footnotes: Vec::new(),
+        adoption_date: None,
     })
 }
 